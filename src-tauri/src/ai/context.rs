@@ -1,8 +1,16 @@
-use crate::data::champion::Champion;
+use crate::data::champion::{
+    Champion, ChampionDetail, ChampionInfo, ChampionSpell, ChampionStats, MAX_LEVEL, MIN_LEVEL,
+};
+use crate::data::diff::{ChangeDirection, EntryRef, StatChange, VersionDiff};
+use crate::data::durability::{self, Durability};
 use crate::data::item::Item;
 use serde::Serialize;
 use std::collections::HashMap;
-use std::fmt::format;
+
+const SPELL_SLOTS: [&str; 4] = ["Q", "W", "E", "R"];
+
+// Levels durability is described at: start, first two ultimate ranks and max level
+const DURABILITY_LEVELS: [u8; 4] = [MIN_LEVEL, 6, 11, MAX_LEVEL];
 
 #[derive(Debug, Serialize, Clone)]
 pub struct ChampionContext {
@@ -13,6 +21,31 @@ pub struct ChampionContext {
     pub resource_type: String,
     pub role_info: RoleInfo,
     pub stats: ChampionStatsContext,
+    // Only filled when built from the per-champion detail file
+    pub passive: Option<AbilityContext>,
+    pub abilities: Vec<AbilityContext>,
+    pub ally_tips: Vec<String>,
+    pub enemy_tips: Vec<String>,
+    // Effective HP at DURABILITY_LEVELS without items
+    pub durability: Vec<Durability>,
+    pub item_build: Option<ItemBuildContext>,
+}
+
+// An item set the user asked about and what it does to the champion's durability
+#[derive(Debug, Serialize, Clone)]
+pub struct ItemBuildContext {
+    pub items: Vec<ItemContext>,
+    pub durability: Vec<Durability>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AbilityContext {
+    pub slot: String,
+    pub name: String,
+    pub description: String,
+    pub cooldown: String,
+    pub cost: String,
+    pub range: String,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -47,48 +80,180 @@ pub struct ChampionStatsContext {
     pub attack_speed_per_level: f64,
 }
 
+impl From<ChampionInfo> for RoleInfo {
+    fn from(info: ChampionInfo) -> Self {
+        Self {
+            attack: info.attack,
+            defense: info.defense,
+            magic: info.magic,
+            difficulty: info.difficulty,
+        }
+    }
+}
+
+impl From<ChampionStats> for ChampionStatsContext {
+    fn from(stats: ChampionStats) -> Self {
+        Self {
+            hp: stats.hp,
+            hp_per_level: stats.hp_per_level,
+            mp: stats.mp,
+            mp_per_level: stats.mp_per_level,
+            move_speed: stats.move_speed,
+            armor: stats.armor,
+            armor_per_level: stats.armor_per_level,
+            spell_block: stats.spell_block,
+            spell_block_per_level: stats.spell_block_per_level,
+            attack_range: stats.attack_range,
+            hp_regen: stats.hp_regen,
+            hp_regen_per_level: stats.hp_regen_per_level,
+            mp_regen: stats.mp_regen,
+            mp_regen_per_level: stats.mp_regen_per_level,
+            crit: stats.crit,
+            crit_per_level: stats.crit_per_level,
+            attack_damage: stats.attack_damage,
+            attack_damage_per_level: stats.attack_damage_per_level,
+            attack_speed: stats.attack_speed,
+            attack_speed_per_level: stats.attack_speed_per_level,
+        }
+    }
+}
+
+fn durability_at_levels(stats: &ChampionStats, items: &[&Item]) -> Vec<Durability> {
+    DURABILITY_LEVELS
+        .iter()
+        .filter_map(|level| durability::durability(stats, *level, items))
+        .collect()
+}
+
 impl From<Champion> for ChampionContext {
     fn from(c: Champion) -> Self {
+        let durability = durability_at_levels(&c.stats, &[]);
+        Self {
+            name: c.name,
+            title: c.title,
+            lore_snippet: c.blurb,
+            roles: c.tags,
+            resource_type: c.partype,
+            role_info: c.info.into(),
+            stats: c.stats.into(),
+            passive: None,
+            abilities: Vec::new(),
+            ally_tips: Vec::new(),
+            enemy_tips: Vec::new(),
+            durability,
+            item_build: None,
+        }
+    }
+}
+
+impl From<ChampionDetail> for ChampionContext {
+    fn from(c: ChampionDetail) -> Self {
+        let passive = AbilityContext {
+            slot: "Passive".to_string(),
+            name: c.passive.name,
+            description: strip_tags(&c.passive.description),
+            cooldown: String::new(),
+            cost: String::new(),
+            range: String::new(),
+        };
+
+        let abilities = c
+            .spells
+            .into_iter()
+            .zip(SPELL_SLOTS)
+            .map(|(spell, slot)| AbilityContext::from_spell(slot, spell))
+            .collect();
+        let durability = durability_at_levels(&c.stats, &[]);
+
         Self {
             name: c.name,
             title: c.title,
             lore_snippet: c.blurb,
             roles: c.tags,
             resource_type: c.partype,
-            role_info: RoleInfo {
-                attack: c.info.attack,
-                defense: c.info.defense,
-                magic: c.info.magic,
-                difficulty: c.info.difficulty,
-            },
-            stats: ChampionStatsContext {
-                hp: c.stats.hp,
-                hp_per_level: c.stats.hp_per_level,
-                mp: c.stats.mp,
-                mp_per_level: c.stats.mp_per_level,
-                move_speed: c.stats.move_speed,
-                armor: c.stats.armor,
-                armor_per_level: c.stats.armor_per_level,
-                spell_block: c.stats.spell_block,
-                spell_block_per_level: c.stats.spell_block_per_level,
-                attack_range: c.stats.attack_range,
-                hp_regen: c.stats.hp_regen,
-                hp_regen_per_level: c.stats.hp_regen_per_level,
-                mp_regen: c.stats.mp_regen,
-                mp_regen_per_level: c.stats.mp_regen_per_level,
-                crit: c.stats.crit,
-                crit_per_level: c.stats.crit_per_level,
-                attack_damage: c.stats.attack_damage,
-                attack_damage_per_level: c.stats.attack_damage_per_level,
-                attack_speed: c.stats.attack_speed,
-                attack_speed_per_level: c.stats.attack_speed_per_level,
-            },
+            role_info: c.info.into(),
+            stats: c.stats.into(),
+            passive: Some(passive),
+            abilities,
+            ally_tips: c.allytips,
+            enemy_tips: c.enemytips,
+            durability,
+            item_build: None,
         }
     }
 }
 
+impl AbilityContext {
+    fn from_spell(slot: &str, spell: ChampionSpell) -> Self {
+        // costBurn is "0" for spells without a cost, costType then holds text like "No Cost"
+        let cost = if spell.cost.iter().all(|c| *c == 0.0) {
+            String::new()
+        } else {
+            format!(
+                "{} {}",
+                spell.cost_burn,
+                strip_tags(&spell.cost_type).trim()
+            )
+        };
+
+        Self {
+            slot: slot.to_string(),
+            name: spell.name,
+            description: strip_tags(&spell.description),
+            cooldown: spell.cooldown_burn,
+            cost,
+            range: spell.range_burn,
+        }
+    }
+
+    fn to_context(&self) -> String {
+        let mut text = format!("{} - {}: {}", self.slot, self.name, self.description);
+        if !self.cooldown.is_empty() {
+            text.push_str(&format!(" Cooldown: {} seconds.", self.cooldown));
+        }
+        if !self.cost.is_empty() {
+            text.push_str(&format!(" Cost: {}.", self.cost));
+        }
+        if !self.range.is_empty() {
+            text.push_str(&format!(" Range: {}.", self.range));
+        }
+        text
+    }
+}
+
+// Data Dragon descriptions carry markup like <br> and <physicalDamage>, which only adds noise to prompts
+fn strip_tags(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                result.push(' ');
+            }
+            '>' => in_tag = false,
+            _ if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 impl ChampionContext {
-    fn to_context(&self) -> Vec<String> {
+    pub fn with_items(mut self, stats: &ChampionStats, items: &[&Item]) -> Self {
+        if !items.is_empty() {
+            self.item_build = Some(ItemBuildContext {
+                items: items
+                    .iter()
+                    .map(|item| ItemContext::from((*item).clone()))
+                    .collect(),
+                durability: durability_at_levels(stats, items),
+            });
+        }
+        self
+    }
+
+    pub fn to_context(&self) -> Vec<String> {
         let roles_str = self.roles.join(", ");
         let basic_description = format!(
             "{}, otherwise known as {}, is a {}. {}",
@@ -152,13 +317,48 @@ impl ChampionContext {
             self.stats.mp_regen_per_level
         );
 
-        vec![
+        let mut context = vec![
             basic_description,
             role_position,
             casting_resource_description,
             stat_growth_description,
             base_stats_description,
-        ]
+        ];
+
+        if let Some(passive) = &self.passive {
+            context.push(format!("{}'s abilities are:", self.name));
+            context.push(passive.to_context());
+            context.extend(self.abilities.iter().map(AbilityContext::to_context));
+        }
+        context.extend(durability_context(
+            &format!("{} without items", self.name),
+            &self.durability,
+        ));
+        if let Some(build) = &self.item_build {
+            context.push(format!("Items being considered for {}:", self.name));
+            context.extend(build.items.iter().map(ItemContext::to_context));
+            context.extend(durability_context(
+                &format!("{} with these items", self.name),
+                &build.durability,
+            ));
+        }
+
+        if !self.ally_tips.is_empty() {
+            context.push(format!(
+                "Tips for playing as or with {}: {}",
+                self.name,
+                self.ally_tips.join(" ")
+            ));
+        }
+        if !self.enemy_tips.is_empty() {
+            context.push(format!(
+                "Tips for playing against {}: {}",
+                self.name,
+                self.enemy_tips.join(" ")
+            ));
+        }
+
+        context
     }
 }
 
@@ -206,3 +406,137 @@ impl From<Item> for ItemContext {
         }
     }
 }
+
+impl ItemContext {
+    fn to_context(&self) -> String {
+        let mut stats: Vec<String> = self
+            .stats
+            .iter()
+            .map(|(stat, value)| format!("{} {}", stat, value))
+            .collect();
+        stats.sort();
+        format!(
+            "{} ({} gold): {}. {}",
+            self.name,
+            self.gold_cost,
+            stats.join(", "),
+            strip_tags(&self.description)
+        )
+    }
+}
+
+// Effective HP per level, followed by what extra resists or HP would be worth at the highest level
+fn durability_context(label: &str, durability: &[Durability]) -> Vec<String> {
+    let Some(last) = durability.last() else {
+        return Vec::new();
+    };
+
+    let per_level = durability
+        .iter()
+        .map(|d| {
+            format!(
+                "level {}: {:.0} HP, {:.0} armor, {:.0} magic resist, {:.0} effective HP against physical and {:.0} against magic damage",
+                d.level, d.hp, d.armor, d.magic_resist, d.physical_effective_hp, d.magic_effective_hp
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    // Effective HP grows linearly with resists, so the marginal value only depends on HP
+    let resist_gain = |resist: f64| {
+        last.hp / durability::damage_multiplier(resist + 10.0)
+            - last.hp / durability::damage_multiplier(resist)
+    };
+    let marginal = format!(
+        "At level {}, +10 armor adds {:.0} effective HP against physical damage, +10 magic resist adds {:.0} against magic damage, and +100 HP adds {:.0} against physical and {:.0} against magic damage.",
+        last.level,
+        resist_gain(last.armor),
+        resist_gain(last.magic_resist),
+        100.0 / durability::damage_multiplier(last.armor),
+        100.0 / durability::damage_multiplier(last.magic_resist)
+    );
+
+    vec![format!("Durability of {}: {}.", label, per_level), marginal]
+}
+
+// Buffs and nerfs between two versions, one line per changed champion or item
+pub fn patch_context(diff: &VersionDiff) -> Vec<String> {
+    let mut context = vec![format!(
+        "Changes from patch {} to patch {}:",
+        diff.from, diff.to
+    )];
+
+    let names = |entries: &[EntryRef]| {
+        entries
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    if !diff.champions_added.is_empty() {
+        context.push(format!("New champions: {}.", names(&diff.champions_added)));
+    }
+    if !diff.champions_removed.is_empty() {
+        context.push(format!(
+            "Removed champions: {}.",
+            names(&diff.champions_removed)
+        ));
+    }
+    if !diff.items_added.is_empty() {
+        context.push(format!("New items: {}.", names(&diff.items_added)));
+    }
+    if !diff.items_removed.is_empty() {
+        context.push(format!("Removed items: {}.", names(&diff.items_removed)));
+    }
+
+    for change in &diff.champion_changes {
+        context.push(format!(
+            "{} ({}): {}",
+            change.name,
+            verdict(&change.stats),
+            describe_stats(&change.stats)
+        ));
+    }
+
+    for change in &diff.item_changes {
+        let mut stats: Vec<StatChange> = change.gold.iter().cloned().collect();
+        stats.extend(change.stats.iter().cloned());
+
+        let mut line = format!("{} ({})", change.name, verdict(&stats));
+        if !stats.is_empty() {
+            line.push_str(&format!(": {}", describe_stats(&stats)));
+        }
+        if let Some(recipe) = &change.recipe {
+            line.push_str(&format!(
+                ". Recipe changed from [{}] to [{}]",
+                recipe.from_before.join(", "),
+                recipe.from_after.join(", ")
+            ));
+        }
+        context.push(line);
+    }
+
+    context
+}
+
+fn describe_stats(stats: &[StatChange]) -> String {
+    stats
+        .iter()
+        .map(|s| format!("{} {} -> {}", s.stat, s.from, s.to))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn verdict(stats: &[StatChange]) -> &'static str {
+    let buffs = stats
+        .iter()
+        .filter(|s| s.direction == ChangeDirection::Buff)
+        .count();
+    let nerfs = stats.len() - buffs;
+    match (buffs, nerfs) {
+        (0, 0) => "adjusted",
+        (_, 0) => "buffed",
+        (0, _) => "nerfed",
+        _ => "adjusted",
+    }
+}
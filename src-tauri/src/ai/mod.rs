@@ -4,7 +4,11 @@ use keyring::Entry;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::{fs, path::PathBuf};
-use tauri::{AppHandle, Manager, Runtime};
+use tauri::{AppHandle, Manager, Runtime, State};
+
+use crate::ai::context::ChampionContext;
+use crate::data::{self, AppState};
+use crate::error::AppError;
 
 const SERVICE_NAME: &str = "my-league-guider";
 const PROFILES_FILENAME: &str = "ai_profiles.json";
@@ -49,10 +53,10 @@ pub struct ChatCompletionResponse {
     pub choices: Vec<ChatCompletionChoice>,
 }
 
-fn get_profiles_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
-    let path = app.path().app_config_dir().map_err(|e| e.to_string())?;
+fn get_profiles_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, AppError> {
+    let path = app.path().app_config_dir()?;
     if !path.exists() {
-        fs::create_dir_all(&path).map_err(|e| e.to_string())?;
+        fs::create_dir_all(&path)?;
     }
     Ok(path.join(PROFILES_FILENAME))
 }
@@ -62,7 +66,7 @@ pub async fn save_ai_profile<R: Runtime>(
     app: AppHandle<R>,
     profile: AiProfile,
     api_key: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let mut profiles = get_ai_profiles(app.clone()).await.unwrap_or_default();
     let is_new_profile = !profiles.iter().any(|p| p.id == profile.id);
 
@@ -73,11 +77,11 @@ pub async fn save_ai_profile<R: Runtime>(
     if !clean_key.is_empty() {
         // Attempt to save
         let entry = Entry::new(SERVICE_NAME, &profile.id)
-            .map_err(|e| format!("Keyring init failed: {}", e))?;
+            .map_err(|e| AppError::Keyring(format!("Keyring init failed: {}", e)))?;
 
         entry
             .set_password(clean_key)
-            .map_err(|e| format!("Failed to write to secure storage: {}", e))?;
+            .map_err(|e| AppError::Keyring(format!("Failed to write to secure storage: {}", e)))?;
 
         // Verify the save worked by reading it back immediately
         match entry.get_password() {
@@ -85,12 +89,16 @@ pub async fn save_ai_profile<R: Runtime>(
                 // Verification successful
                 println!("Key saved and verified for profile {}", profile.id);
             }
-            Ok(_) => return Err("Secure storage data mismatch (saved vs read).".to_string()),
+            Ok(_) => {
+                return Err(AppError::Keyring(
+                    "Secure storage data mismatch (saved vs read).".to_string(),
+                ))
+            }
             Err(e) => {
-                return Err(format!(
+                return Err(AppError::Keyring(format!(
                     "Secure storage verification failed. The OS did not persist the key: {}",
                     e
-                ))
+                )))
             }
         }
     } else if is_new_profile {
@@ -109,28 +117,28 @@ pub async fn save_ai_profile<R: Runtime>(
 
     // 3. Save metadata to JSON file
     let path = get_profiles_path(&app)?;
-    let json = serde_json::to_string_pretty(&profiles).map_err(|e| e.to_string())?;
-    fs::write(path, json).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&profiles)?;
+    fs::write(path, json)?;
 
     Ok(())
 }
 
 #[tauri::command]
-pub async fn get_ai_profiles<R: Runtime>(app: AppHandle<R>) -> Result<Vec<AiProfile>, String> {
+pub async fn get_ai_profiles<R: Runtime>(app: AppHandle<R>) -> Result<Vec<AiProfile>, AppError> {
     let path = get_profiles_path(&app)?;
     if !path.exists() {
         return Ok(Vec::new());
     }
 
-    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
-    let profiles: Vec<AiProfile> = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    let content = fs::read_to_string(path)?;
+    let profiles: Vec<AiProfile> = serde_json::from_str(&content)?;
     Ok(profiles)
 }
 
 #[tauri::command]
-pub async fn delete_ai_profile<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+pub async fn delete_ai_profile<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), AppError> {
     // 1. Remove from Keyring
-    let entry = Entry::new(SERVICE_NAME, &id).map_err(|e| e.to_string())?;
+    let entry = Entry::new(SERVICE_NAME, &id)?;
     let _ = entry.delete_credential(); // Ignore error if key doesn't exist
 
     // 2. Remove from JSON file
@@ -138,8 +146,8 @@ pub async fn delete_ai_profile<R: Runtime>(app: AppHandle<R>, id: String) -> Res
     profiles.retain(|p| p.id != id);
 
     let path = get_profiles_path(&app)?;
-    let json = serde_json::to_string_pretty(&profiles).map_err(|e| e.to_string())?;
-    fs::write(path, json).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(&profiles)?;
+    fs::write(path, json)?;
 
     Ok(())
 }
@@ -149,7 +157,7 @@ pub async fn send_chat_request(
     profile: AiProfile,
     messages: Vec<ChatMessage>,
     temperature: Option<f32>,
-) -> Result<ChatCompletionResponse, String> {
+) -> Result<ChatCompletionResponse, AppError> {
     let client = Client::new();
 
     println!(
@@ -157,23 +165,25 @@ pub async fn send_chat_request(
         profile.id, SERVICE_NAME
     );
 
-    let entry =
-        Entry::new(SERVICE_NAME, &profile.id).map_err(|e| format!("Keyring init error: {}", e))?;
+    let entry = Entry::new(SERVICE_NAME, &profile.id)
+        .map_err(|e| AppError::Keyring(format!("Keyring init error: {}", e)))?;
 
     let api_key = match entry.get_password() {
         Ok(key) => key,
         Err(keyring::Error::NoEntry) => {
-            return Err(
+            return Err(AppError::Keyring(
                 "API Key not found in storage. Please Edit the profile and re-enter the key."
                     .to_string(),
-            );
+            ));
         }
         Err(keyring::Error::Ambiguous(_)) => {
-            return Err("Multiple keys found for this ID. Storage is ambiguous.".to_string());
+            return Err(AppError::Keyring(
+                "Multiple keys found for this ID. Storage is ambiguous.".to_string(),
+            ));
         }
         Err(e) => {
             println!("CRITICAL KEYRING ERROR: {:?}", e);
-            return Err(format!("OS Secure Storage Error: {}", e));
+            return Err(AppError::Keyring(format!("OS Secure Storage Error: {}", e)));
         }
     };
 
@@ -191,19 +201,74 @@ pub async fn send_chat_request(
         .json(&request_body)
         .send()
         .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+        .map_err(|e| AppError::Network(format!("Request failed: {}", e)))?;
 
-    if !response.status().is_success() {
+    let status = response.status();
+    if !status.is_success() {
         let error_text = response
             .text()
             .await
             .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("API Error: {}", error_text));
+        return Err(AppError::Provider {
+            status: status.as_u16(),
+            message: error_text,
+        });
     }
 
     let completion_response = response
         .json::<ChatCompletionResponse>()
         .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
+        .map_err(|e| AppError::Parse(format!("Failed to parse response: {}", e)))?;
     Ok(completion_response)
 }
+
+// Builds the prompt context for a champion, including abilities when the detail file can be fetched.
+// item_ids adds the item set and its effect on effective HP, so build advice can cite numbers.
+#[tauri::command]
+pub async fn get_champion_context<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+    id: String,
+    item_ids: Option<Vec<String>>,
+) -> Result<Vec<String>, AppError> {
+    let context = match data::champion_detail(app, &state, &id).await {
+        Ok(detail) => ChampionContext::from(detail),
+        Err(e) => {
+            println!(
+                "Champion detail unavailable for {}, using summary: {}",
+                id, e
+            );
+            let data = state.data.lock()?;
+            let champion = data
+                .champions
+                .as_ref()
+                .and_then(|champ_data| champ_data.data.get(&id))
+                .cloned()
+                .ok_or(e)?;
+            ChampionContext::from(champion)
+        }
+    };
+
+    let item_ids = item_ids.unwrap_or_default();
+    let context = if item_ids.is_empty() {
+        context
+    } else {
+        let data = state.data.lock()?;
+        let champion = data::find_champion(&data, &id)?;
+        let items = data::find_items(&data, &item_ids)?;
+        context.with_items(&champion.stats, &items)
+    };
+
+    Ok(context.to_context())
+}
+
+// Patch changes between two local versions, phrased as buffs and nerfs for the prompt
+#[tauri::command]
+pub async fn get_patch_context<R: Runtime>(
+    app: AppHandle<R>,
+    from: String,
+    to: String,
+) -> Result<Vec<String>, AppError> {
+    let diff = data::diff_versions(app, from, to).await?;
+    Ok(context::patch_context(&diff))
+}
@@ -0,0 +1,249 @@
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, Read, Write},
+    path::{Component, Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::data::{assets::IMAGE_DIR, manager::DATA_FILES, manifest::Manifest};
+use crate::error::AppError;
+
+// Written at the root of exported archives, describes the whole snapshot
+pub const EXPORT_INFO_FILE: &str = "export.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportInfo {
+    pub version: String,
+    pub locales: Vec<String>,
+    // Seconds since the Unix epoch
+    pub exported_at: u64,
+    // Every file in the archive except export.json itself
+    pub manifest: Manifest,
+}
+
+// Extracts the data files of an archive into dest as {version}/{locale}/{file}.
+// Supports Riot's dragontail-{version}.tgz and zips exported by export_data_archive.
+pub fn extract(archive_path: &Path, dest: &Path) -> Result<(), AppError> {
+    let name = archive_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    if name.ends_with(".tgz") || name.ends_with(".tar.gz") {
+        extract_dragontail(archive_path, dest)
+    } else if name.ends_with(".zip") {
+        extract_zip(archive_path, dest)
+    } else {
+        Err(AppError::InvalidInput(
+            "Unsupported archive, expected a .tgz or .zip file".to_string(),
+        ))
+    }
+}
+
+// Packs a version directory into a zip at dest, written to a temporary file and renamed when complete.
+// The image cache is left out, it can be rebuilt with cache_images and import would skip it anyway.
+pub fn export(version_dir: &Path, version: &str, dest: &Path) -> Result<ExportInfo, AppError> {
+    let mut files = Vec::new();
+    collect_files(version_dir, Path::new(""), &mut files)?;
+    files.retain(|relative| !relative.starts_with(IMAGE_DIR));
+
+    let mut locales: Vec<String> = files
+        .iter()
+        .filter_map(|relative| relative.components().next())
+        .filter_map(|component| component.as_os_str().to_str())
+        .map(|locale| locale.to_string())
+        .collect();
+    locales.sort();
+    locales.dedup();
+
+    let mut tmp_name = dest.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    let result = write_export(version_dir, version, &files, &tmp_path, locales);
+    match result {
+        Ok(info) => {
+            fs::rename(&tmp_path, dest)?;
+            Ok(info)
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&tmp_path);
+            Err(e)
+        }
+    }
+}
+
+fn write_export(
+    version_dir: &Path,
+    version: &str,
+    files: &[PathBuf],
+    tmp_path: &Path,
+    locales: Vec<String>,
+) -> Result<ExportInfo, AppError> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut writer = ZipWriter::new(File::create(tmp_path)?);
+    let mut manifest = Manifest::default();
+
+    for relative in files {
+        let content = fs::read(version_dir.join(relative))?;
+        // Zip entries always use forward slashes
+        let name = Path::new(version)
+            .join(relative)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        writer.start_file(name.as_str(), options)?;
+        writer.write_all(&content)?;
+        manifest.record(&name, &content);
+    }
+
+    let info = ExportInfo {
+        version: version.to_string(),
+        locales,
+        exported_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+        manifest,
+    };
+    let info_json = serde_json::to_string_pretty(&info)?;
+    writer.start_file(EXPORT_INFO_FILE, options)?;
+    writer.write_all(info_json.as_bytes())?;
+
+    writer.finish()?;
+    Ok(info)
+}
+
+// Relative paths of every file under dir, skipping leftovers of interrupted writes
+fn collect_files(dir: &Path, relative: &Path, files: &mut Vec<PathBuf>) -> Result<(), AppError> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let entry_relative = relative.join(entry.file_name());
+
+        if path.is_dir() {
+            collect_files(&path, &entry_relative, files)?;
+        } else if path.extension().is_none_or(|extension| extension != "tmp") {
+            files.push(entry_relative);
+        }
+    }
+
+    files.sort();
+    Ok(())
+}
+
+// dragontail lays files out as {version}/data/{locale}/{file}, images and other data are skipped
+fn extract_dragontail(archive_path: &Path, dest: &Path) -> Result<(), AppError> {
+    let file = File::open(archive_path)?;
+    let mut archive = tar::Archive::new(GzDecoder::new(BufReader::new(file)));
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path()?.into_owned();
+        let target = match path_parts(&path).as_deref() {
+            Some([version, data, locale, rest @ ..]) if data == "data" => {
+                data_target(version, locale, rest)
+            }
+            _ => None,
+        };
+        if let Some(target) = target {
+            write_entry(&mut entry, &dest.join(target))?;
+        }
+    }
+
+    Ok(())
+}
+
+// Exported zips mirror the versions directory: {version}/{locale}/{file}
+fn extract_zip(archive_path: &Path, dest: &Path) -> Result<(), AppError> {
+    let file = File::open(archive_path)?;
+    let mut archive = zip::ZipArchive::new(BufReader::new(file))?;
+
+    // Older exports or hand-made zips may lack export.json, the per-locale manifests still apply
+    let export_info: Option<ExportInfo> = match archive.by_name(EXPORT_INFO_FILE) {
+        Ok(mut entry) => {
+            let mut content = String::new();
+            entry.read_to_string(&mut content)?;
+            Some(
+                serde_json::from_str(&content)
+                    .map_err(|e| AppError::Parse(format!("Export Parse: {}", e)))?,
+            )
+        }
+        Err(_) => None,
+    };
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+
+        let target = match entry
+            .enclosed_name()
+            .as_deref()
+            .and_then(path_parts)
+            .as_deref()
+        {
+            Some([version, locale, rest @ ..]) => data_target(version, locale, rest),
+            _ => None,
+        };
+        let Some(target) = target else {
+            continue;
+        };
+
+        let name = entry.name().to_string();
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        if let Some(info) = &export_info {
+            if info.manifest.files.contains_key(&name) && !info.manifest.matches(&name, &content) {
+                return Err(AppError::Corrupt(vec![name]));
+            }
+        }
+        write_entry(&mut content.as_slice(), &dest.join(target))?;
+    }
+
+    Ok(())
+}
+
+// None for anything that could escape the destination, like ".." or absolute paths
+pub(crate) fn path_parts(path: &Path) -> Option<Vec<String>> {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .map(|component| match component {
+            Component::Normal(part) => part.to_str().map(|part| part.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+// Only the files the app reads are extracted
+fn data_target(version: &str, locale: &str, rest: &[String]) -> Option<PathBuf> {
+    let relative = match rest {
+        [file_name] if DATA_FILES.contains(&file_name.as_str()) || file_name == "manifest.json" => {
+            PathBuf::from(file_name)
+        }
+        [dir, file_name] if dir == "champion" && file_name.ends_with(".json") => {
+            Path::new(dir).join(file_name)
+        }
+        _ => return None,
+    };
+    Some(Path::new(version).join(locale).join(relative))
+}
+
+fn write_entry(entry: &mut impl Read, target: &Path) -> Result<(), AppError> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = File::create(target)?;
+    io::copy(entry, &mut file)?;
+    Ok(())
+}
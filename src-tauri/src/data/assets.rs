@@ -0,0 +1,205 @@
+use image::ImageFormat;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::Cursor,
+    path::{Path, PathBuf},
+};
+use tauri::{
+    http::{header::CONTENT_TYPE, Request, Response, StatusCode},
+    AppHandle, Runtime,
+};
+
+use crate::data::{archive::path_parts, manager::league_data_dir, Image, LeagueDataState};
+
+// league-asset://localhost/{version}/{path under img/}, e.g. /14.10.1/champion/Ahri.png.
+// Adding ?x=&y=&w=&h= crops that rectangle out of the file, which is how sprite icons are served.
+// Windows webviews reach the same handler through http://league-asset.localhost/.
+pub const ASSET_SCHEME: &str = "league-asset";
+
+// Cached images live in versions/{version}/img, next to the locale directories
+pub const IMAGE_DIR: &str = "img";
+
+// One image to cache: where it lives on the CDN and where it goes under versions/{version}/img
+#[derive(Debug, Clone)]
+pub struct ImageRequest {
+    pub cdn_path: String,
+    pub relative_path: String,
+}
+
+// Icons and sprite sheets for everything loaded in memory, deduplicated by target path
+pub fn image_requests(data: &LeagueDataState, version: &str) -> Vec<ImageRequest> {
+    let mut images: Vec<&Image> = Vec::new();
+    if let Some(champions) = &data.champions {
+        images.extend(champions.data.values().map(|c| &c.image));
+    }
+    if let Some(items) = &data.items {
+        images.extend(items.data.values().filter_map(|i| i.image.as_ref()));
+    }
+    if let Some(summoner_spells) = &data.summoner_spells {
+        images.extend(summoner_spells.data.values().map(|s| &s.image));
+    }
+    for detail in data.champion_details.values() {
+        images.push(&detail.passive.image);
+        images.extend(detail.spells.iter().map(|s| &s.image));
+    }
+
+    let mut requests = BTreeMap::new();
+    for image in images {
+        for relative_path in [
+            format!("{}/{}", image.group, image.full),
+            format!("sprite/{}", image.sprite),
+        ] {
+            requests
+                .entry(relative_path.clone())
+                .or_insert_with(|| format!("cdn/{}/img/{}", version, relative_path));
+        }
+    }
+
+    // Rune icons are not versioned on the CDN, e.g. cdn/img/perk-images/Styles/Precision/...
+    if let Some(runes) = &data.runes {
+        let icons = runes.iter().flat_map(|path| {
+            std::iter::once(&path.icon).chain(
+                path.slots
+                    .iter()
+                    .flat_map(|slot| slot.runes.iter().map(|rune| &rune.icon)),
+            )
+        });
+        for icon in icons {
+            requests
+                .entry(icon.clone())
+                .or_insert_with(|| format!("cdn/img/{}", icon));
+        }
+    }
+
+    requests
+        .into_iter()
+        .filter(|(relative_path, _)| path_parts(Path::new(relative_path)).is_some())
+        .map(|(relative_path, cdn_path)| ImageRequest {
+            cdn_path,
+            relative_path,
+        })
+        .collect()
+}
+
+pub fn handle_asset_request<R: Runtime>(
+    app: &AppHandle<R>,
+    request: &Request<Vec<u8>>,
+) -> Response<Vec<u8>> {
+    match serve_asset(app, request) {
+        Ok(response) => response,
+        Err((status, message)) => Response::builder()
+            .status(status)
+            .header(CONTENT_TYPE, "text/plain")
+            .body(message.into_bytes())
+            .unwrap(),
+    }
+}
+
+fn serve_asset<R: Runtime>(
+    app: &AppHandle<R>,
+    request: &Request<Vec<u8>>,
+) -> Result<Response<Vec<u8>>, (StatusCode, String)> {
+    let uri = request.uri();
+    let not_found = || (StatusCode::NOT_FOUND, format!("Asset not found: {}", uri));
+
+    // Percent-decoded and checked so the path can never leave the img directory
+    let decoded = percent_decode(uri.path());
+    let parts = path_parts(Path::new(decoded.trim_start_matches('/')))
+        .filter(|parts| parts.len() >= 2)
+        .ok_or((
+            StatusCode::BAD_REQUEST,
+            format!("Invalid asset path: {}", uri),
+        ))?;
+
+    let file_path: PathBuf = league_data_dir(app)
+        .join("versions")
+        .join(&parts[0])
+        .join(IMAGE_DIR)
+        .join(parts[1..].iter().collect::<PathBuf>());
+    let content = fs::read(&file_path).map_err(|_| not_found())?;
+
+    let content = match crop_rect(uri.query()) {
+        Some(rect) => crop(&content, rect)?,
+        None => content,
+    };
+
+    Response::builder()
+        .header(CONTENT_TYPE, content_type(&file_path))
+        .body(content)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+// Same shape as the x, y, w, h of an Image
+fn crop_rect(query: Option<&str>) -> Option<(u32, u32, u32, u32)> {
+    let params: HashMap<&str, u32> = query?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .filter_map(|(key, value)| Some((key, value.parse().ok()?)))
+        .collect();
+
+    Some((
+        *params.get("x")?,
+        *params.get("y")?,
+        *params.get("w")?,
+        *params.get("h")?,
+    ))
+}
+
+// The rectangle comes straight from the query string, so a bad one is a 400 rather than a panic
+fn crop(
+    content: &[u8],
+    (x, y, w, h): (u32, u32, u32, u32),
+) -> Result<Vec<u8>, (StatusCode, String)> {
+    let sprite = image::load_from_memory(content)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let fits = |start: u32, length: u32, size: u32| {
+        start.checked_add(length).is_some_and(|end| end <= size)
+    };
+    if w == 0 || h == 0 || !fits(x, w, sprite.width()) || !fits(y, h, sprite.height()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Crop rectangle is outside of the image".to_string(),
+        ));
+    }
+
+    let mut output = Cursor::new(Vec::new());
+    sprite
+        .crop_imm(x, y, w, h)
+        .write_to(&mut output, ImageFormat::Png)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(output.into_inner())
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
+        _ => "image/png",
+    }
+}
+
+// Rune icon paths and a few champion file names contain characters browsers escape
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
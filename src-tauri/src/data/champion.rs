@@ -5,32 +5,45 @@ use std::collections::HashMap;
 // Represent the data package we will get from API
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ChampionData {
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default)]
     pub data_type: String,
+    #[serde(default)]
     pub format: String,
+    #[serde(default)]
     pub version: String,
     pub data: HashMap<String, Champion>,
 }
 
 // Let's use datadragon as the way to get data
+// Only the identifying fields are required, anything else missing is defaulted and
+// shows up in the validation report instead of failing the whole load
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Champion {
     pub id: String,
     pub key: String,
     pub name: String,
+    #[serde(default)]
     pub title: String,
 
+    #[serde(default)]
     pub version: String,
 
+    #[serde(default)]
     pub blurb: String,
+    #[serde(default)]
     pub info: ChampionInfo,
+    #[serde(default)]
     pub image: Image,
+    #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
     pub partype: String,
+    #[serde(default)]
     pub stats: ChampionStats,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct ChampionInfo {
     pub attack: u8,
     pub defense: u8,
@@ -38,7 +51,8 @@ pub struct ChampionInfo {
     pub difficulty: u8,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct ChampionStats {
     pub hp: f64,
     #[serde(rename = "hpperlevel")]
@@ -95,3 +109,258 @@ pub struct ChampionStats {
     #[serde(rename = "attackspeed")]
     pub attack_speed: f64,
 }
+
+impl ChampionStats {
+    // Every stat keyed by its Data Dragon name, in declaration order
+    pub fn entries(&self) -> [(&'static str, f64); 20] {
+        [
+            ("hp", self.hp),
+            ("hpperlevel", self.hp_per_level),
+            ("mp", self.mp),
+            ("mpperlevel", self.mp_per_level),
+            ("movespeed", self.move_speed),
+            ("armor", self.armor),
+            ("armorperlevel", self.armor_per_level),
+            ("spellblock", self.spell_block),
+            ("spellblockperlevel", self.spell_block_per_level),
+            ("attackrange", self.attack_range),
+            ("hpregen", self.hp_regen),
+            ("hpregenperlevel", self.hp_regen_per_level),
+            ("mpregen", self.mp_regen),
+            ("mpregenperlevel", self.mp_regen_per_level),
+            ("crit", self.crit),
+            ("critperlevel", self.crit_per_level),
+            ("attackdamage", self.attack_damage),
+            ("attackdamageperlevel", self.attack_damage_per_level),
+            ("attackspeedperlevel", self.attack_speed_per_level),
+            ("attackspeed", self.attack_speed),
+        ]
+    }
+}
+
+pub const MIN_LEVEL: u8 = 1;
+pub const MAX_LEVEL: u8 = 18;
+
+// Stats resolved for one champion level, per-level growth already applied
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LevelStats {
+    pub level: u8,
+    pub hp: f64,
+    pub mp: f64,
+    pub move_speed: f64,
+    pub armor: f64,
+    pub spell_block: f64,
+    pub attack_range: f64,
+    pub hp_regen: f64,
+    pub mp_regen: f64,
+    pub crit: f64,
+    pub attack_damage: f64,
+    // Attacks per second
+    pub attack_speed: f64,
+}
+
+impl ChampionStats {
+    // None outside of levels 1-18
+    pub fn at_level(&self, level: u8) -> Option<LevelStats> {
+        if !(MIN_LEVEL..=MAX_LEVEL).contains(&level) {
+            return None;
+        }
+
+        let factor = growth_factor(level);
+        let grow = |base: f64, per_level: f64| base + per_level * factor;
+        Some(LevelStats {
+            level,
+            hp: grow(self.hp, self.hp_per_level),
+            mp: grow(self.mp, self.mp_per_level),
+            move_speed: self.move_speed,
+            armor: grow(self.armor, self.armor_per_level),
+            spell_block: grow(self.spell_block, self.spell_block_per_level),
+            attack_range: self.attack_range,
+            hp_regen: grow(self.hp_regen, self.hp_regen_per_level),
+            mp_regen: grow(self.mp_regen, self.mp_regen_per_level),
+            crit: grow(self.crit, self.crit_per_level),
+            attack_damage: grow(self.attack_damage, self.attack_damage_per_level),
+            // attackspeedperlevel is a percentage bonus on top of the base attack speed
+            attack_speed: self.attack_speed * (1.0 + self.attack_speed_per_level / 100.0 * factor),
+        })
+    }
+}
+
+// Riot's growth curve: per-level gains get slightly larger the higher the level
+fn growth_factor(level: u8) -> f64 {
+    let n = f64::from(level) - 1.0;
+    n * (0.7025 + 0.0175 * n)
+}
+
+// Per-champion file (champion/{id}.json), only fetched on demand
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChampionDetailData {
+    #[serde(rename = "type", default)]
+    pub data_type: String,
+    #[serde(default)]
+    pub format: String,
+    #[serde(default)]
+    pub version: String,
+    pub data: HashMap<String, ChampionDetail>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChampionDetail {
+    pub id: String,
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub title: String,
+
+    #[serde(default)]
+    pub lore: String,
+    #[serde(default)]
+    pub blurb: String,
+    #[serde(default)]
+    pub allytips: Vec<String>,
+    #[serde(default)]
+    pub enemytips: Vec<String>,
+
+    #[serde(default)]
+    pub info: ChampionInfo,
+    #[serde(default)]
+    pub image: Image,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub partype: String,
+    #[serde(default)]
+    pub stats: ChampionStats,
+
+    #[serde(default)]
+    pub skins: Vec<ChampionSkin>,
+    // Always Q, W, E, R in that order
+    #[serde(default)]
+    pub spells: Vec<ChampionSpell>,
+    #[serde(default)]
+    pub passive: ChampionPassive,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChampionSkin {
+    pub id: String,
+    pub num: u32,
+    pub name: String,
+    #[serde(default)]
+    pub chromas: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChampionSpell {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tooltip: String,
+    #[serde(default)]
+    pub maxrank: u32,
+
+    // Per rank values, "Burn" fields are the same values joined by "/"
+    #[serde(default)]
+    pub cooldown: Vec<f64>,
+    #[serde(rename = "cooldownBurn", default)]
+    pub cooldown_burn: String,
+    #[serde(default)]
+    pub cost: Vec<f64>,
+    #[serde(rename = "costBurn", default)]
+    pub cost_burn: String,
+    #[serde(default)]
+    pub range: Vec<f64>,
+    #[serde(rename = "rangeBurn", default)]
+    pub range_burn: String,
+    #[serde(rename = "costType", default)]
+    pub cost_type: String,
+    pub resource: Option<String>,
+
+    #[serde(default)]
+    pub image: Image,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ChampionPassive {
+    pub name: String,
+    pub description: String,
+    pub image: Image,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::assert_close;
+
+    // Garen's Data Dragon stats
+    fn stats() -> ChampionStats {
+        ChampionStats {
+            hp: 690.0,
+            hp_per_level: 98.0,
+            armor: 38.0,
+            armor_per_level: 4.2,
+            attack_damage: 69.0,
+            attack_damage_per_level: 4.5,
+            attack_speed: 0.625,
+            attack_speed_per_level: 3.65,
+            move_speed: 340.0,
+            attack_range: 175.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn level_one_is_the_base_stats() {
+        let level = stats().at_level(1).unwrap();
+        assert_close(level.hp, 690.0);
+        assert_close(level.armor, 38.0);
+        assert_close(level.attack_damage, 69.0);
+        assert_close(level.attack_speed, 0.625);
+    }
+
+    #[test]
+    fn level_two_applies_the_first_growth_step() {
+        // growth factor at level 2 is 0.7025 + 0.0175 = 0.72
+        let level = stats().at_level(2).unwrap();
+        assert_close(level.hp, 690.0 + 98.0 * 0.72);
+        assert_close(level.armor, 38.0 + 4.2 * 0.72);
+        assert_close(level.attack_damage, 69.0 + 4.5 * 0.72);
+    }
+
+    #[test]
+    fn level_eighteen_applies_seventeen_full_levels() {
+        let level = stats().at_level(18).unwrap();
+        assert_close(level.hp, 2356.0);
+        assert_close(level.armor, 109.4);
+        assert_close(level.attack_damage, 145.5);
+    }
+
+    #[test]
+    fn attack_speed_growth_is_a_percentage_of_the_base() {
+        let stats = stats();
+        assert_close(
+            stats.at_level(2).unwrap().attack_speed,
+            0.625 * (1.0 + 0.0365 * 0.72),
+        );
+        assert_close(
+            stats.at_level(18).unwrap().attack_speed,
+            0.625 * (1.0 + 0.0365 * 17.0),
+        );
+    }
+
+    #[test]
+    fn move_speed_and_range_do_not_grow() {
+        let level = stats().at_level(18).unwrap();
+        assert_close(level.move_speed, 340.0);
+        assert_close(level.attack_range, 175.0);
+    }
+
+    #[test]
+    fn levels_outside_one_to_eighteen_are_rejected() {
+        assert!(stats().at_level(0).is_none());
+        assert!(stats().at_level(19).is_none());
+    }
+}
@@ -0,0 +1,328 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::{
+    champion::{Champion, LevelStats, MAX_LEVEL, MIN_LEVEL},
+    diff::EntryRef,
+};
+
+// Values closer than this count as a tie, per-level growth leaves float noise behind
+const EPSILON: f64 = 1e-6;
+
+struct ComparedStat {
+    // Field name in LevelStats
+    key: &'static str,
+    // Used in crossover descriptions
+    label: &'static str,
+    value: fn(&LevelStats) -> f64,
+}
+
+const COMPARED_STATS: [ComparedStat; 7] = [
+    ComparedStat {
+        key: "hp",
+        label: "HP",
+        value: |s| s.hp,
+    },
+    ComparedStat {
+        key: "armor",
+        label: "armor",
+        value: |s| s.armor,
+    },
+    ComparedStat {
+        key: "spell_block",
+        label: "MR",
+        value: |s| s.spell_block,
+    },
+    ComparedStat {
+        key: "attack_damage",
+        label: "AD",
+        value: |s| s.attack_damage,
+    },
+    ComparedStat {
+        key: "attack_speed",
+        label: "attack speed",
+        value: |s| s.attack_speed,
+    },
+    ComparedStat {
+        key: "move_speed",
+        label: "move speed",
+        value: |s| s.move_speed,
+    },
+    ComparedStat {
+        key: "attack_range",
+        label: "range",
+        value: |s| s.attack_range,
+    },
+];
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct LevelRange {
+    pub from: u8,
+    pub to: u8,
+}
+
+impl Default for LevelRange {
+    fn default() -> Self {
+        Self {
+            from: MIN_LEVEL,
+            to: MAX_LEVEL,
+        }
+    }
+}
+
+impl LevelRange {
+    pub fn is_valid(&self) -> bool {
+        MIN_LEVEL <= self.from && self.from <= self.to && self.to <= MAX_LEVEL
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ChampionComparison {
+    pub champions: Vec<EntryRef>,
+    pub levels: Vec<LevelComparison>,
+    pub crossovers: Vec<Crossover>,
+}
+
+// One row of the table, stats are in the same order as ChampionComparison::champions
+#[derive(Debug, Serialize, Clone)]
+pub struct LevelComparison {
+    pub level: u8,
+    pub stats: Vec<LevelStats>,
+    pub leaders: Vec<StatLeader>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StatLeader {
+    pub stat: String,
+    // Several ids when they are tied
+    pub champion_ids: Vec<String>,
+    pub value: f64,
+}
+
+// A pair of champions swapping places on a stat, e.g. "Darius out-stats Garen in AD from level 6"
+#[derive(Debug, Serialize, Clone)]
+pub struct Crossover {
+    pub stat: String,
+    pub level: u8,
+    pub leader_id: String,
+    pub trailer_id: String,
+    pub description: String,
+}
+
+pub fn compare_champions(champions: &[&Champion], range: LevelRange) -> ChampionComparison {
+    let levels: Vec<LevelComparison> = (range.from..=range.to)
+        .map(|level| {
+            let stats: Vec<LevelStats> = champions
+                .iter()
+                .filter_map(|champion| champion.stats.at_level(level))
+                .collect();
+            let leaders = COMPARED_STATS
+                .iter()
+                .map(|stat| leader(stat, champions, &stats))
+                .collect();
+            LevelComparison {
+                level,
+                stats,
+                leaders,
+            }
+        })
+        .collect();
+
+    let mut crossovers = Vec::new();
+    for stat in &COMPARED_STATS {
+        for a in 0..champions.len() {
+            for b in a + 1..champions.len() {
+                crossovers.extend(pair_crossovers(stat, &levels, champions, (a, b)));
+            }
+        }
+    }
+    crossovers.sort_by_key(|crossover| crossover.level);
+
+    ChampionComparison {
+        champions: champions
+            .iter()
+            .map(|champion| EntryRef {
+                id: champion.id.clone(),
+                name: champion.name.clone(),
+            })
+            .collect(),
+        levels,
+        crossovers,
+    }
+}
+
+fn leader(stat: &ComparedStat, champions: &[&Champion], stats: &[LevelStats]) -> StatLeader {
+    let value = stats.iter().map(stat.value).fold(f64::MIN, f64::max);
+    StatLeader {
+        stat: stat.key.to_string(),
+        champion_ids: champions
+            .iter()
+            .zip(stats)
+            .filter(|(_, stats)| ((stat.value)(stats) - value).abs() < EPSILON)
+            .map(|(champion, _)| champion.id.clone())
+            .collect(),
+        value,
+    }
+}
+
+// Levels where champion a and b swap the lead on one stat, ties don't count as a swap
+fn pair_crossovers(
+    stat: &ComparedStat,
+    levels: &[LevelComparison],
+    champions: &[&Champion],
+    (a, b): (usize, usize),
+) -> Vec<Crossover> {
+    let mut crossovers = Vec::new();
+    let mut last_lead: Option<bool> = None;
+
+    for row in levels {
+        let delta = (stat.value)(&row.stats[a]) - (stat.value)(&row.stats[b]);
+        if delta.abs() < EPSILON {
+            continue;
+        }
+
+        let a_leads = delta > 0.0;
+        if last_lead.is_some_and(|lead| lead != a_leads) {
+            let (leader, trailer) = if a_leads {
+                (champions[a], champions[b])
+            } else {
+                (champions[b], champions[a])
+            };
+            crossovers.push(Crossover {
+                stat: stat.key.to_string(),
+                level: row.level,
+                leader_id: leader.id.clone(),
+                trailer_id: trailer.id.clone(),
+                description: format!(
+                    "{} out-stats {} in {} from level {}",
+                    leader.name, trailer.name, stat.label, row.level
+                ),
+            });
+        }
+        last_lead = Some(a_leads);
+    }
+
+    crossovers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{champion::ChampionStats, test_util::assert_close};
+
+    fn champion(id: &str, attack_damage: f64, attack_damage_per_level: f64) -> Champion {
+        Champion {
+            id: id.to_string(),
+            key: String::new(),
+            name: id.to_string(),
+            title: String::new(),
+            version: String::new(),
+            blurb: String::new(),
+            info: Default::default(),
+            image: Default::default(),
+            tags: Vec::new(),
+            partype: String::new(),
+            stats: ChampionStats {
+                attack_damage,
+                attack_damage_per_level,
+                ..Default::default()
+            },
+        }
+    }
+
+    // Hand-made rows with only AD set, level n holds the nth pair
+    fn ad_rows(values: &[(f64, f64)]) -> Vec<LevelComparison> {
+        let base = ChampionStats::default().at_level(1).unwrap();
+        values
+            .iter()
+            .zip(1..)
+            .map(|(&(a, b), level)| LevelComparison {
+                level,
+                stats: vec![
+                    LevelStats {
+                        attack_damage: a,
+                        ..base.clone()
+                    },
+                    LevelStats {
+                        attack_damage: b,
+                        ..base.clone()
+                    },
+                ],
+                leaders: Vec::new(),
+            })
+            .collect()
+    }
+
+    fn attack_damage() -> &'static ComparedStat {
+        COMPARED_STATS
+            .iter()
+            .find(|stat| stat.key == "attack_damage")
+            .unwrap()
+    }
+
+    #[test]
+    fn attack_damage_lines_cross_at_the_expected_level() {
+        // Early starts 10 AD ahead and Late gains 2 more per level, so Late leads once the
+        // growth factor passes 5: it is 4.845 at level 7 and 5.775 at level 8
+        let early = champion("Early", 70.0, 2.0);
+        let late = champion("Late", 60.0, 4.0);
+        let comparison = compare_champions(&[&early, &late], LevelRange::default());
+
+        assert_eq!(comparison.crossovers.len(), 1);
+        let crossover = &comparison.crossovers[0];
+        assert_eq!(crossover.stat, "attack_damage");
+        assert_eq!(crossover.level, 8);
+        assert_eq!(crossover.leader_id, "Late");
+        assert_eq!(crossover.trailer_id, "Early");
+        assert_eq!(
+            crossover.description,
+            "Late out-stats Early in AD from level 8"
+        );
+
+        let ad_leader = |level: usize| {
+            comparison.levels[level - 1]
+                .leaders
+                .iter()
+                .find(|leader| leader.stat == "attack_damage")
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(ad_leader(7).champion_ids, ["Early"]);
+        assert_eq!(ad_leader(8).champion_ids, ["Late"]);
+        assert_close(ad_leader(18).value, 60.0 + 4.0 * 17.0);
+    }
+
+    #[test]
+    fn tied_leaders_are_all_listed() {
+        let a = champion("A", 60.0, 3.0);
+        let b = champion("B", 60.0, 0.0);
+        let comparison = compare_champions(&[&a, &b], LevelRange { from: 1, to: 2 });
+
+        let level_one = &comparison.levels[0].leaders;
+        let ad = level_one
+            .iter()
+            .find(|leader| leader.stat == "attack_damage")
+            .unwrap();
+        assert_eq!(ad.champion_ids, ["A", "B"]);
+        assert_close(ad.value, 60.0);
+
+        // Pulling ahead from a tie is not a swap
+        assert!(comparison.crossovers.is_empty());
+    }
+
+    #[test]
+    fn ties_between_leads_are_skipped() {
+        let (a, b) = (champion("A", 0.0, 0.0), champion("B", 0.0, 0.0));
+        let champions = [&a, &b];
+
+        // A leads, they tie, A leads again: no swap
+        let rows = ad_rows(&[(70.0, 60.0), (65.0, 65.0), (70.0, 60.0)]);
+        assert!(pair_crossovers(attack_damage(), &rows, &champions, (0, 1)).is_empty());
+
+        // A leads, they tie, B leads: one swap, at the level B actually leads
+        let rows = ad_rows(&[(70.0, 60.0), (65.0, 65.0), (60.0, 70.0)]);
+        let crossovers = pair_crossovers(attack_damage(), &rows, &champions, (0, 1));
+        assert_eq!(crossovers.len(), 1);
+        assert_eq!(crossovers[0].level, 3);
+        assert_eq!(crossovers[0].leader_id, "B");
+    }
+}
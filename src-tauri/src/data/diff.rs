@@ -0,0 +1,389 @@
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::data::{
+    champion::{ChampionData, ChampionStats},
+    item::{Item, ItemData},
+};
+
+// Differences below this are float noise from the JSON, not balance changes
+const EPSILON: f64 = 1e-6;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct VersionDiff {
+    pub from: String,
+    pub to: String,
+    pub champions_added: Vec<EntryRef>,
+    pub champions_removed: Vec<EntryRef>,
+    pub champion_changes: Vec<ChampionChange>,
+    pub items_added: Vec<EntryRef>,
+    pub items_removed: Vec<EntryRef>,
+    pub item_changes: Vec<ItemChange>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EntryRef {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ChampionChange {
+    pub id: String,
+    pub name: String,
+    pub stats: Vec<StatChange>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ItemChange {
+    pub id: String,
+    pub name: String,
+    pub gold: Option<StatChange>,
+    pub stats: Vec<StatChange>,
+    pub recipe: Option<RecipeChange>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StatChange {
+    pub stat: String,
+    pub from: f64,
+    pub to: f64,
+    pub delta: f64,
+    pub direction: ChangeDirection,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeDirection {
+    Buff,
+    Nerf,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RecipeChange {
+    pub from_before: Vec<String>,
+    pub from_after: Vec<String>,
+}
+
+impl StatChange {
+    // Every champion and item stat is better when higher, only gold cost is the other way round
+    fn new(stat: &str, from: f64, to: f64, higher_is_better: bool) -> Option<Self> {
+        let delta = to - from;
+        if delta.abs() < EPSILON {
+            return None;
+        }
+        let direction = if (delta > 0.0) == higher_is_better {
+            ChangeDirection::Buff
+        } else {
+            ChangeDirection::Nerf
+        };
+
+        Some(Self {
+            stat: stat.to_string(),
+            from,
+            to,
+            delta,
+            direction,
+        })
+    }
+}
+
+pub fn diff_versions(
+    from: &str,
+    to: &str,
+    old_champs: &ChampionData,
+    old_items: &ItemData,
+    new_champs: &ChampionData,
+    new_items: &ItemData,
+) -> VersionDiff {
+    let mut diff = VersionDiff {
+        from: from.to_string(),
+        to: to.to_string(),
+        champions_added: Vec::new(),
+        champions_removed: Vec::new(),
+        champion_changes: Vec::new(),
+        items_added: Vec::new(),
+        items_removed: Vec::new(),
+        item_changes: Vec::new(),
+    };
+
+    for id in all_keys(&old_champs.data, &new_champs.data) {
+        match (old_champs.data.get(id), new_champs.data.get(id)) {
+            (None, Some(champ)) => diff.champions_added.push(EntryRef {
+                id: id.clone(),
+                name: champ.name.clone(),
+            }),
+            (Some(champ), None) => diff.champions_removed.push(EntryRef {
+                id: id.clone(),
+                name: champ.name.clone(),
+            }),
+            (Some(old), Some(new)) => {
+                let stats = diff_champion_stats(&old.stats, &new.stats);
+                if !stats.is_empty() {
+                    diff.champion_changes.push(ChampionChange {
+                        id: id.clone(),
+                        name: new.name.clone(),
+                        stats,
+                    });
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    for id in all_keys(&old_items.data, &new_items.data) {
+        match (old_items.data.get(id), new_items.data.get(id)) {
+            (None, Some(item)) => diff.items_added.push(EntryRef {
+                id: id.clone(),
+                name: item.name.clone(),
+            }),
+            (Some(item), None) => diff.items_removed.push(EntryRef {
+                id: id.clone(),
+                name: item.name.clone(),
+            }),
+            (Some(old), Some(new)) => {
+                if let Some(change) = diff_item(id, old, new) {
+                    diff.item_changes.push(change);
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    diff
+}
+
+// Sorted so the diff comes out in a stable order
+fn all_keys<'a, T>(
+    old: &'a HashMap<String, T>,
+    new: &'a HashMap<String, T>,
+) -> BTreeSet<&'a String> {
+    old.keys().chain(new.keys()).collect()
+}
+
+fn diff_champion_stats(old: &ChampionStats, new: &ChampionStats) -> Vec<StatChange> {
+    old.entries()
+        .into_iter()
+        .zip(new.entries())
+        .filter_map(|((stat, from), (_, to))| StatChange::new(stat, from, to, true))
+        .collect()
+}
+
+fn diff_item(id: &str, old: &Item, new: &Item) -> Option<ItemChange> {
+    let gold = StatChange::new("gold", old.gold.total as f64, new.gold.total as f64, false);
+
+    let stat_names: BTreeSet<&String> = old.stats.keys().chain(new.stats.keys()).collect();
+    let stats: Vec<StatChange> = stat_names
+        .into_iter()
+        .filter_map(|stat| {
+            let from = old.stats.get(stat).copied().unwrap_or(0.0);
+            let to = new.stats.get(stat).copied().unwrap_or(0.0);
+            StatChange::new(stat, from, to, true)
+        })
+        .collect();
+
+    let mut from_before = old.from.clone();
+    let mut from_after = new.from.clone();
+    from_before.sort();
+    from_after.sort();
+    let recipe = (from_before != from_after).then_some(RecipeChange {
+        from_before,
+        from_after,
+    });
+
+    if gold.is_none() && stats.is_empty() && recipe.is_none() {
+        return None;
+    }
+
+    Some(ItemChange {
+        id: id.to_string(),
+        name: new.name.clone(),
+        gold,
+        stats,
+        recipe,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{champion::Champion, item::ItemGold, test_util::assert_close};
+
+    fn champion(id: &str, stats: ChampionStats) -> Champion {
+        Champion {
+            id: id.to_string(),
+            key: String::new(),
+            name: id.to_string(),
+            title: String::new(),
+            version: String::new(),
+            blurb: String::new(),
+            info: Default::default(),
+            image: Default::default(),
+            tags: Vec::new(),
+            partype: String::new(),
+            stats,
+        }
+    }
+
+    fn champions(champions: Vec<Champion>) -> ChampionData {
+        ChampionData {
+            data_type: String::new(),
+            format: String::new(),
+            version: String::new(),
+            data: champions.into_iter().map(|c| (c.id.clone(), c)).collect(),
+        }
+    }
+
+    fn item(gold: u32, stats: &[(&str, f64)], from: &[&str]) -> Item {
+        Item {
+            name: "Item".to_string(),
+            gold: ItemGold {
+                total: gold,
+                ..Default::default()
+            },
+            stats: stats
+                .iter()
+                .map(|(stat, value)| (stat.to_string(), *value))
+                .collect(),
+            from: from.iter().map(|id| id.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn items(items: Vec<(&str, Item)>) -> ItemData {
+        ItemData {
+            data_type: String::new(),
+            version: String::new(),
+            basic: Item::default(),
+            data: items
+                .into_iter()
+                .map(|(id, item)| (id.to_string(), item))
+                .collect(),
+            groups: Vec::new(),
+            tree: Vec::new(),
+        }
+    }
+
+    fn diff(old_champs: ChampionData, new_champs: ChampionData) -> VersionDiff {
+        diff_versions(
+            "1.1",
+            "1.2",
+            &old_champs,
+            &items(Vec::new()),
+            &new_champs,
+            &items(Vec::new()),
+        )
+    }
+
+    fn ids(entries: &[EntryRef]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.id.as_str()).collect()
+    }
+
+    #[test]
+    fn detects_added_and_removed_champions_and_items() {
+        let old_champs = champions(vec![
+            champion("Ahri", ChampionStats::default()),
+            champion("Garen", ChampionStats::default()),
+        ]);
+        let new_champs = champions(vec![
+            champion("Garen", ChampionStats::default()),
+            champion("Zaahen", ChampionStats::default()),
+        ]);
+        let old_items = items(vec![("1001", item(300, &[], &[]))]);
+        let new_items = items(vec![("1004", item(400, &[], &[]))]);
+
+        let diff = diff_versions(
+            "1.1",
+            "1.2",
+            &old_champs,
+            &old_items,
+            &new_champs,
+            &new_items,
+        );
+        assert_eq!(ids(&diff.champions_added), ["Zaahen"]);
+        assert_eq!(ids(&diff.champions_removed), ["Ahri"]);
+        assert_eq!(ids(&diff.items_added), ["1004"]);
+        assert_eq!(ids(&diff.items_removed), ["1001"]);
+        assert!(diff.champion_changes.is_empty() && diff.item_changes.is_empty());
+    }
+
+    #[test]
+    fn changes_below_epsilon_are_ignored() {
+        let old = ChampionStats {
+            hp: 600.0,
+            armor: 30.0,
+            ..Default::default()
+        };
+        let new = ChampionStats {
+            hp: 600.0 + EPSILON / 2.0,
+            armor: 32.0,
+            ..Default::default()
+        };
+
+        let diff = diff(
+            champions(vec![champion("Garen", old)]),
+            champions(vec![champion("Garen", new)]),
+        );
+        let stats = &diff.champion_changes[0].stats;
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].stat, "armor");
+        assert_close(stats[0].delta, 2.0);
+        assert_eq!(stats[0].direction, ChangeDirection::Buff);
+    }
+
+    #[test]
+    fn unchanged_champions_are_left_out() {
+        let stats = ChampionStats {
+            hp: 600.0,
+            ..Default::default()
+        };
+        let diff = diff(
+            champions(vec![champion("Garen", stats.clone())]),
+            champions(vec![champion("Garen", stats)]),
+        );
+        assert!(diff.champion_changes.is_empty());
+    }
+
+    #[test]
+    fn gold_cost_going_up_is_a_nerf() {
+        let change = diff_item("3031", &item(3400, &[], &[]), &item(3600, &[], &[])).unwrap();
+        let gold = change.gold.unwrap();
+        assert_close(gold.delta, 200.0);
+        assert_eq!(gold.direction, ChangeDirection::Nerf);
+
+        let change = diff_item("3031", &item(3600, &[], &[]), &item(3400, &[], &[])).unwrap();
+        assert_eq!(change.gold.unwrap().direction, ChangeDirection::Buff);
+    }
+
+    #[test]
+    fn stats_on_only_one_side_count_from_zero() {
+        let old = item(3000, &[("FlatPhysicalDamageMod", 60.0)], &[]);
+        let new = item(3000, &[("PercentAttackSpeedMod", 0.25)], &[]);
+
+        let change = diff_item("3031", &old, &new).unwrap();
+        assert!(change.gold.is_none());
+        assert_eq!(change.stats.len(), 2);
+
+        let removed = &change.stats[0];
+        assert_eq!(removed.stat, "FlatPhysicalDamageMod");
+        assert_close(removed.to, 0.0);
+        assert_eq!(removed.direction, ChangeDirection::Nerf);
+
+        let added = &change.stats[1];
+        assert_eq!(added.stat, "PercentAttackSpeedMod");
+        assert_close(added.from, 0.0);
+        assert_eq!(added.direction, ChangeDirection::Buff);
+    }
+
+    #[test]
+    fn recipe_order_does_not_matter() {
+        let old = item(3000, &[], &["1038", "1037"]);
+        let reordered = item(3000, &[], &["1037", "1038"]);
+        assert!(diff_item("3031", &old, &reordered).is_none());
+
+        let changed = item(3000, &[], &["1037", "1036"]);
+        let recipe = diff_item("3031", &old, &changed).unwrap().recipe.unwrap();
+        assert_eq!(recipe.from_before, ["1037", "1038"]);
+        assert_eq!(recipe.from_after, ["1036", "1037"]);
+    }
+}
@@ -0,0 +1,60 @@
+use futures_util::{stream, StreamExt};
+use serde::Serialize;
+use std::{fmt::Display, future::Future};
+
+// Per-file outcome of a batch of downloads, returned by updates, repairs and image caching
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct DownloadReport {
+    pub succeeded: Vec<String>,
+    // Already on disk, nothing was requested
+    pub skipped: Vec<String>,
+    pub failed: Vec<FailedDownload>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FailedDownload {
+    pub file: String,
+    pub error: String,
+}
+
+impl DownloadReport {
+    pub fn record<T, E: Display>(&mut self, file: &str, result: &Result<T, E>) {
+        match result {
+            Ok(_) => self.succeeded.push(file.to_string()),
+            Err(error) => self.failed.push(FailedDownload {
+                file: file.to_string(),
+                error: error.to_string(),
+            }),
+        }
+    }
+
+    // One line per failed file, used when a batch has to fail as a whole
+    pub fn failure_summary(&self) -> String {
+        self.failed
+            .iter()
+            .map(|failed| format!("{}: {}", failed.file, failed.error))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+// Runs job for every name with at most limit of them in flight, results come back in completion order.
+// Jobs share the DataManager (and so its client) by reference instead of being spawned as tasks.
+pub async fn run_bounded<T, E, F, Fut>(
+    limit: usize,
+    names: impl IntoIterator<Item = String>,
+    job: F,
+) -> Vec<(String, Result<T, E>)>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    stream::iter(names)
+        .map(|name| {
+            let future = job(name.clone());
+            async move { (name, future.await) }
+        })
+        .buffer_unordered(limit.max(1))
+        .collect()
+        .await
+}
@@ -0,0 +1,161 @@
+use serde::Serialize;
+
+use crate::data::{
+    champion::ChampionStats,
+    durability::damage_multiplier,
+    item::{self, Item},
+};
+
+// Item stat keys as they appear in item.json, attack speed and crit are fractions (0.25 = 25%)
+pub const FLAT_ATTACK_DAMAGE: &str = "FlatPhysicalDamageMod";
+pub const PERCENT_ATTACK_SPEED: &str = "PercentAttackSpeedMod";
+pub const FLAT_CRIT_CHANCE: &str = "FlatCritChanceMod";
+
+pub const ATTACK_SPEED_CAP: f64 = 2.5;
+pub const CRIT_DAMAGE_MULTIPLIER: f64 = 1.75;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AutoAttackDps {
+    pub level: u8,
+    pub attack_damage: f64,
+    // Attacks per second after the cap, uncapped shows how much attack speed is wasted
+    pub attack_speed: f64,
+    pub uncapped_attack_speed: f64,
+    pub crit_chance: f64,
+    pub crit_multiplier: f64,
+    // Average damage of one auto attack with crits, before armor
+    pub average_hit: f64,
+    pub target_armor: f64,
+    pub raw_dps: f64,
+    // After the target's armor
+    pub dps: f64,
+}
+
+// Sustained auto-attack DPS at a level with an item set against a target's armor, None outside levels 1-18.
+// Bonus attack speed scales with the champion's base attack speed, which Data Dragon uses as the ratio.
+pub fn auto_attack_dps(
+    stats: &ChampionStats,
+    level: u8,
+    items: &[&Item],
+    target_armor: f64,
+) -> Option<AutoAttackDps> {
+    let base = stats.at_level(level)?;
+
+    let attack_damage = base.attack_damage + item::total_stat(items, FLAT_ATTACK_DAMAGE);
+    let uncapped_attack_speed =
+        base.attack_speed + stats.attack_speed * item::total_stat(items, PERCENT_ATTACK_SPEED);
+    let attack_speed = uncapped_attack_speed.min(ATTACK_SPEED_CAP);
+    let crit_chance =
+        (base.crit / 100.0 + item::total_stat(items, FLAT_CRIT_CHANCE)).clamp(0.0, 1.0);
+
+    let average_hit = attack_damage * (1.0 + crit_chance * (CRIT_DAMAGE_MULTIPLIER - 1.0));
+    let raw_dps = average_hit * attack_speed;
+
+    Some(AutoAttackDps {
+        level,
+        attack_damage,
+        attack_speed,
+        uncapped_attack_speed,
+        crit_chance,
+        crit_multiplier: CRIT_DAMAGE_MULTIPLIER,
+        average_hit,
+        target_armor,
+        raw_dps,
+        dps: raw_dps * damage_multiplier(target_armor),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::assert_close;
+    use std::collections::HashMap;
+
+    fn stats() -> ChampionStats {
+        ChampionStats {
+            attack_damage: 60.0,
+            attack_damage_per_level: 3.0,
+            attack_speed: 0.625,
+            attack_speed_per_level: 2.0,
+            ..Default::default()
+        }
+    }
+
+    fn item(stats: &[(&str, f64)]) -> Item {
+        Item {
+            stats: stats
+                .iter()
+                .map(|(stat, value)| (stat.to_string(), *value))
+                .collect::<HashMap<_, _>>(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn level_one_without_items_is_attack_damage_times_attack_speed() {
+        let dps = auto_attack_dps(&stats(), 1, &[], 0.0).unwrap();
+
+        assert_close(dps.attack_damage, 60.0);
+        assert_close(dps.attack_speed, 0.625);
+        assert_close(dps.crit_chance, 0.0);
+        assert_close(dps.dps, 60.0 * 0.625);
+    }
+
+    #[test]
+    fn growth_applies_at_higher_levels() {
+        let dps = auto_attack_dps(&stats(), 18, &[], 0.0).unwrap();
+        let factor = 17.0 * (0.7025 + 0.0175 * 17.0);
+
+        assert_close(dps.attack_damage, 60.0 + 3.0 * factor);
+        assert_close(dps.attack_speed, 0.625 * (1.0 + 0.02 * factor));
+    }
+
+    #[test]
+    fn item_stats_add_damage_and_scale_attack_speed_by_the_ratio() {
+        let sword = item(&[(FLAT_ATTACK_DAMAGE, 10.0)]);
+        let dagger = item(&[(PERCENT_ATTACK_SPEED, 0.4)]);
+        let dps = auto_attack_dps(&stats(), 1, &[&sword, &dagger], 0.0).unwrap();
+
+        assert_close(dps.attack_damage, 70.0);
+        assert_close(dps.attack_speed, 0.625 * 1.4);
+        assert_close(dps.dps, 70.0 * 0.625 * 1.4);
+    }
+
+    #[test]
+    fn attack_speed_is_capped() {
+        let dagger = item(&[(PERCENT_ATTACK_SPEED, 5.0)]);
+        let dps = auto_attack_dps(&stats(), 1, &[&dagger], 0.0).unwrap();
+
+        assert_close(dps.uncapped_attack_speed, 0.625 * 6.0);
+        assert_close(dps.attack_speed, ATTACK_SPEED_CAP);
+        assert_close(dps.raw_dps, 60.0 * ATTACK_SPEED_CAP);
+    }
+
+    #[test]
+    fn crits_use_the_crit_multiplier_and_cap_at_full_chance() {
+        let cloak = item(&[(FLAT_CRIT_CHANCE, 0.25)]);
+        let dps = auto_attack_dps(&stats(), 1, &[&cloak], 0.0).unwrap();
+        assert_close(dps.average_hit, 60.0 * (1.0 + 0.25 * 0.75));
+
+        let edge = item(&[(FLAT_CRIT_CHANCE, 0.8)]);
+        let dps = auto_attack_dps(&stats(), 1, &[&edge, &edge], 0.0).unwrap();
+        assert_close(dps.crit_chance, 1.0);
+        assert_close(dps.average_hit, 60.0 * CRIT_DAMAGE_MULTIPLIER);
+    }
+
+    #[test]
+    fn armor_reduces_damage() {
+        let dps = auto_attack_dps(&stats(), 1, &[], 100.0).unwrap();
+        assert_close(dps.dps, dps.raw_dps / 2.0);
+
+        // Negative armor amplifies damage, capped at double
+        let dps = auto_attack_dps(&stats(), 1, &[], -100.0).unwrap();
+        assert_close(dps.dps, dps.raw_dps * 1.5);
+    }
+
+    #[test]
+    fn levels_outside_the_range_are_rejected() {
+        assert!(auto_attack_dps(&stats(), 0, &[], 0.0).is_none());
+        assert!(auto_attack_dps(&stats(), 19, &[], 0.0).is_none());
+    }
+}
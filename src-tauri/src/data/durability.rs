@@ -0,0 +1,53 @@
+use serde::Serialize;
+
+use crate::data::{
+    champion::ChampionStats,
+    item::{self, Item},
+};
+
+// Item stat keys as they appear in item.json
+pub const FLAT_HP: &str = "FlatHPPoolMod";
+pub const FLAT_ARMOR: &str = "FlatArmorMod";
+pub const FLAT_MAGIC_RESIST: &str = "FlatSpellBlockMod";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Durability {
+    pub level: u8,
+    pub hp: f64,
+    pub armor: f64,
+    pub magic_resist: f64,
+    // Raw damage needed to kill the champion from full HP
+    pub physical_effective_hp: f64,
+    pub magic_effective_hp: f64,
+    // Share of incoming damage removed by armor / MR, 0.0 - 1.0 (negative for negative resists)
+    pub physical_reduction: f64,
+    pub magic_reduction: f64,
+}
+
+// Level-scaled HP and resists plus the flat bonuses of the given items, None outside levels 1-18
+pub fn durability(stats: &ChampionStats, level: u8, items: &[&Item]) -> Option<Durability> {
+    let base = stats.at_level(level)?;
+    let hp = base.hp + item::total_stat(items, FLAT_HP);
+    let armor = base.armor + item::total_stat(items, FLAT_ARMOR);
+    let magic_resist = base.spell_block + item::total_stat(items, FLAT_MAGIC_RESIST);
+
+    Some(Durability {
+        level,
+        hp,
+        armor,
+        magic_resist,
+        physical_effective_hp: hp / damage_multiplier(armor),
+        magic_effective_hp: hp / damage_multiplier(magic_resist),
+        physical_reduction: 1.0 - damage_multiplier(armor),
+        magic_reduction: 1.0 - damage_multiplier(magic_resist),
+    })
+}
+
+// Share of damage that goes through a resist: 100 / (100 + r), and 2 - 100 / (100 - r) below zero
+pub fn damage_multiplier(resist: f64) -> f64 {
+    if resist >= 0.0 {
+        100.0 / (100.0 + resist)
+    } else {
+        2.0 - 100.0 / (100.0 - resist)
+    }
+}
@@ -0,0 +1,450 @@
+use reqwest::{
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER},
+    Client, Response, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use crate::settings::AppSettings;
+
+// Outcome of a single attempt, only Retry errors are attempted again
+#[derive(Debug)]
+pub enum RequestError {
+    Retry {
+        message: String,
+        retry_after: Option<Duration>,
+    },
+    Fail(String),
+}
+
+impl From<reqwest::Error> for RequestError {
+    fn from(e: reqwest::Error) -> Self {
+        // Dropped connections and timeouts are worth another try, anything else is not
+        if e.is_timeout() || e.is_connect() || e.is_request() || e.is_body() {
+            RequestError::Retry {
+                message: e.to_string(),
+                retry_after: None,
+            }
+        } else {
+            RequestError::Fail(e.to_string())
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            max_retries: settings.max_retries,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+
+    // Exponential backoff with jitter: half of the delay is fixed, the other half random
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay);
+        let half = exponential / 2;
+        half + half.mul_f64(random_fraction())
+    }
+}
+
+// One client (and connection pool) for every DataManager, rebuilt only when the timeouts change
+static SHARED_CLIENT: Mutex<Option<((u64, u64), Client)>> = Mutex::new(None);
+
+pub fn shared_client(settings: &AppSettings) -> Client {
+    let key = (settings.connect_timeout_secs, settings.read_timeout_secs);
+    let Ok(mut shared) = SHARED_CLIENT.lock() else {
+        return build_client(settings);
+    };
+
+    match shared.as_ref() {
+        Some((shared_key, client)) if *shared_key == key => client.clone(),
+        _ => {
+            let client = build_client(settings);
+            *shared = Some((key, client.clone()));
+            client
+        }
+    }
+}
+
+fn build_client(settings: &AppSettings) -> Client {
+    Client::builder()
+        .connect_timeout(Duration::from_secs(settings.connect_timeout_secs))
+        .read_timeout(Duration::from_secs(settings.read_timeout_secs))
+        .build()
+        .unwrap_or_else(|e| {
+            println!("Failed to build HTTP client, using defaults: {}", e);
+            Client::new()
+        })
+}
+
+// Runs attempt until it succeeds, fails permanently or the policy runs out of retries
+pub async fn with_retry<T, F, Fut>(policy: &RetryPolicy, mut attempt: F) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, RequestError>>,
+{
+    let mut retries = 0;
+    loop {
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(RequestError::Fail(message)) => return Err(message),
+            Err(RequestError::Retry {
+                message,
+                retry_after,
+            }) => {
+                if retries >= policy.max_retries {
+                    return Err(message);
+                }
+                let delay = retry_after
+                    .map(|delay| delay.min(policy.max_delay))
+                    .unwrap_or_else(|| policy.backoff(retries));
+                println!(
+                    "Request failed ({}), retrying in {:?} ({}/{})",
+                    message,
+                    delay,
+                    retries + 1,
+                    policy.max_retries
+                );
+                tokio::time::sleep(delay).await;
+                retries += 1;
+            }
+        }
+    }
+}
+
+// Sends a GET and sorts the response into success, retryable or fatal
+pub async fn get(client: &Client, url: &str) -> Result<Response, RequestError> {
+    let response = client.get(url).send().await?;
+    check_status(response, url)
+}
+
+// ETag and Last-Modified of a previous response, sent back to let the server answer 304
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    fn from_response(response: &Response) -> Self {
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string())
+        };
+        Self {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+}
+
+pub enum Conditional {
+    NotModified,
+    Modified {
+        body: String,
+        validators: Validators,
+    },
+}
+
+// Like get, but a 304 comes back as NotModified instead of an error
+pub async fn get_conditional(
+    client: &Client,
+    url: &str,
+    validators: &Validators,
+) -> Result<Conditional, RequestError> {
+    let mut request = client.get(url);
+    if let Some(etag) = &validators.etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &validators.last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+
+    let response = request.send().await?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(Conditional::NotModified);
+    }
+
+    let response = check_status(response, url)?;
+    let validators = Validators::from_response(&response);
+    Ok(Conditional::Modified {
+        body: response.text().await?,
+        validators,
+    })
+}
+
+fn check_status(response: Response, url: &str) -> Result<Response, RequestError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let message = format!("HTTP {} from {}", status, url);
+    if status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+    {
+        Err(RequestError::Retry {
+            message,
+            retry_after: parse_retry_after(&response),
+        })
+    } else {
+        Err(RequestError::Fail(message))
+    }
+}
+
+// Retry-After is either a number of seconds or an HTTP date
+fn parse_retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+// Randomly seeded hasher, good enough for jitter without pulling in an RNG crate
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tauri::http;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    fn policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+        }
+    }
+
+    fn response(status: u16, retry_after: Option<&str>) -> Response {
+        let mut builder = http::Response::builder().status(status);
+        if let Some(value) = retry_after {
+            builder = builder.header(RETRY_AFTER, value);
+        }
+        Response::from(builder.body("").unwrap())
+    }
+
+    fn retry_after_of(result: Result<Response, RequestError>) -> Option<Duration> {
+        match result {
+            Err(RequestError::Retry { retry_after, .. }) => retry_after,
+            other => panic!("expected a retryable error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    // Serves one canned response per connection, in order
+    async fn stub_server(responses: Vec<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/versions.json", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            for body in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    let read = socket.read(&mut buffer).await.unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                socket.write_all(body.as_bytes()).await.unwrap();
+                socket.shutdown().await.unwrap();
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn backoff_stays_between_half_and_full_exponential_delay() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        };
+
+        for retry in 0..4 {
+            let exponential = Duration::from_millis(500 * 2u64.pow(retry));
+            let delay = policy.backoff(retry);
+            assert!(
+                delay >= exponential / 2 && delay <= exponential,
+                "{:?}",
+                delay
+            );
+        }
+    }
+
+    #[test]
+    fn backoff_is_capped_at_max_delay() {
+        let policy = RetryPolicy {
+            max_retries: 100,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        };
+
+        for retry in [10, 31, 32, 100] {
+            let delay = policy.backoff(retry);
+            assert!(delay >= Duration::from_secs(15) && delay <= Duration::from_secs(30));
+        }
+    }
+
+    #[test]
+    fn server_errors_and_throttling_are_retried() {
+        for status in [500, 502, 503, 429, 408] {
+            assert!(
+                matches!(
+                    check_status(response(status, None), "url"),
+                    Err(RequestError::Retry { .. })
+                ),
+                "HTTP {} should be retried",
+                status
+            );
+        }
+    }
+
+    #[test]
+    fn client_errors_are_not_retried() {
+        for status in [400, 403, 404] {
+            assert!(
+                matches!(
+                    check_status(response(status, None), "url"),
+                    Err(RequestError::Fail(_))
+                ),
+                "HTTP {} should not be retried",
+                status
+            );
+        }
+        assert!(check_status(response(200, None), "url").is_ok());
+    }
+
+    #[test]
+    fn retry_after_in_seconds() {
+        let retry_after = retry_after_of(check_status(response(503, Some("120")), "url"));
+        assert_eq!(retry_after, Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn retry_after_as_http_date() {
+        let date = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(60));
+        let retry_after = retry_after_of(check_status(response(503, Some(&date)), "url")).unwrap();
+        // The date only has second precision
+        assert!(retry_after > Duration::from_secs(58) && retry_after <= Duration::from_secs(60));
+
+        let past = httpdate::fmt_http_date(SystemTime::now() - Duration::from_secs(60));
+        let retry_after = retry_after_of(check_status(response(503, Some(&past)), "url"));
+        assert_eq!(retry_after, Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn invalid_retry_after_is_ignored() {
+        let retry_after = retry_after_of(check_status(response(503, Some("soon")), "url"));
+        assert_eq!(retry_after, None);
+    }
+
+    #[tokio::test]
+    async fn with_retry_stops_on_permanent_failure() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<(), String> = with_retry(&policy(5), || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(RequestError::Fail("HTTP 404".to_string()))
+        })
+        .await;
+
+        assert_eq!(result, Err("HTTP 404".to_string()));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn with_retry_gives_up_after_max_retries() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<(), String> = with_retry(&policy(3), || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(RequestError::Retry {
+                message: "HTTP 503".to_string(),
+                retry_after: None,
+            })
+        })
+        .await;
+
+        assert_eq!(result, Err("HTTP 503".to_string()));
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn with_retry_succeeds_after_transient_failures() {
+        let attempts = AtomicU32::new(0);
+        let result = with_retry(&policy(3), || async {
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(RequestError::Retry {
+                    message: "HTTP 503".to_string(),
+                    retry_after: Some(Duration::from_secs(60)),
+                })
+            } else {
+                Ok("done")
+            }
+        })
+        .await;
+
+        // Retry-After is capped by max_delay, so this doesn't actually wait a minute
+        assert_eq!(result, Ok("done"));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn mock_server_503_with_retry_after_then_200() {
+        let url = stub_server(vec![
+            "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 9\r\nConnection: close\r\n\r\n[\"14.10\"]",
+        ])
+        .await;
+        let client = Client::new();
+
+        // The first answer is seen as retryable and carries the server's delay
+        let first = get(&client, &url).await;
+        assert_eq!(retry_after_of(first), Some(Duration::from_secs(1)));
+
+        let body = with_retry(&policy(2), || async {
+            let response = get(&client, &url).await?;
+            Ok(response.text().await?)
+        })
+        .await;
+        assert_eq!(body, Ok("[\"14.10\"]".to_string()));
+    }
+}
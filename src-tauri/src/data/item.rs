@@ -5,9 +5,11 @@ use crate::data::Image;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ItemData {
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default)]
     pub data_type: String,
+    #[serde(default)]
     pub version: String,
+    #[serde(default)]
     pub basic: Item,
     pub data: HashMap<String, Item>,
 
@@ -32,7 +34,7 @@ pub struct ItemTree {
     pub tags: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Item {
     #[serde(default)]
     pub name: String,
@@ -107,3 +109,8 @@ pub struct ItemRune {
     #[serde(rename = "type")]
     pub rune_type: Option<String>,
 }
+
+// Sum of one stat (e.g. FlatArmorMod) over a set of items, items without it count as zero
+pub fn total_stat(items: &[&Item], stat: &str) -> f64 {
+    items.iter().filter_map(|item| item.stats.get(stat)).sum()
+}
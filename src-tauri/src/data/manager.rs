@@ -1,59 +1,398 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::{fs, path::PathBuf, str};
-use tauri::{AppHandle, Manager, Runtime};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fs,
+    future::Future,
+    path::{Path, PathBuf},
+    str,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
 
-use crate::data::{champion::ChampionData, item::ItemData};
+use crate::data::{
+    archive::{self, ExportInfo},
+    assets::{ImageRequest, IMAGE_DIR},
+    champion::{ChampionData, ChampionDetail, ChampionDetailData},
+    download::{self, DownloadReport},
+    http::{self, Conditional, RequestError, RetryPolicy, Validators},
+    item::ItemData,
+    manifest::Manifest,
+    rune::RunePath,
+    schema::{self, ValidationReport},
+    summoner::SummonerData,
+    LeagueDataState,
+};
+use crate::error::AppError;
+use crate::settings;
 
-const VERSIONS_URL: &str = "https://ddragon.leagueoflegends.com/api/versions.json";
-const BASE_CDN_URL: &str = "https://ddragon.leagueoflegends.com/cdn";
-const LANG_CODE: &str = "en_US";
+// Paths relative to a Data Dragon mirror, see AppSettings::data_mirrors
+const VERSIONS_PATH: &str = "api/versions.json";
+const LANGUAGES_PATH: &str = "cdn/languages.json";
+const VERSIONS_CACHE_FILE: &str = "versions_cache.json";
+
+// A locale dir being replaced is parked as versions/{version}/{locale}.old until the swap is done
+const PARKED_SUFFIX: &str = ".old";
+
+pub const PROGRESS_EVENT: &str = "data-update-progress";
+pub const COMPLETE_EVENT: &str = "data-update-complete";
+
+// Minimum time between two progress events for the same file
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+// Files downloaded from data/{locale}/ for every version
+pub(crate) const DATA_FILES: [&str; 4] = [
+    "champion.json",
+    "item.json",
+    "runesReforged.json",
+    "summoner.json",
+];
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DataStatus {
     pub current_version: Option<String>,
     pub latest_version: String,
     pub is_up_to_date: bool,
+    // Files of the current version that fail their manifest check, fixable with repair_data
+    pub corrupt_files: Vec<String>,
+    // Data Dragon could not be reached, latest_version is the last one seen at checked_at
+    pub is_stale: bool,
+    // Unix seconds of the last successful versions.json check
+    pub checked_at: Option<u64>,
+}
+
+// Last versions.json we got, with the validators needed to ask for it conditionally
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct VersionsCache {
+    versions: Vec<String>,
+    validators: Validators,
+    checked_at: u64,
+}
+
+pub struct VersionsList {
+    pub versions: Vec<String>,
+    pub is_stale: bool,
+    pub checked_at: Option<u64>,
+}
+
+// Payload of PROGRESS_EVENT, sent for every chunk while fetching and once per later phase
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateProgress {
+    pub phase: UpdatePhase,
+    pub file: Option<String>,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdatePhase {
+    Fetching,
+    Validating,
+    Loading,
+}
+
+// Payload of COMPLETE_EVENT, sent once when update_data finishes either way
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateComplete {
+    pub version: Option<String>,
+    pub success: bool,
+    pub error: Option<AppError>,
+    pub report: Option<DownloadReport>,
+}
+
+// Result of update_data: the version that is now active and what was downloaded for it
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateReport {
+    pub version: String,
+    pub files: DownloadReport,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocalVersion {
+    pub version: String,
+    // Locales with a complete data set for this version
+    pub locales: Vec<String>,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportedData {
+    pub version: String,
+    pub locale: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LocaleInfo {
+    pub locale: String,
+    // Whether the data for the current local version is already cached in this locale
+    pub is_downloaded: bool,
 }
 
 pub struct DataManager<R: Runtime> {
     app: AppHandle<R>,
     client: Client,
+    retry_policy: RetryPolicy,
+    // Base URLs without trailing slash, tried in order
+    mirrors: Vec<String>,
+    locale: String,
+    keep_versions: usize,
+    concurrency: usize,
+    cancel_token: Option<CancellationToken>,
+    // Only an update drives the progress bar, other downloads stay quiet
+    report_progress: bool,
 }
 
 impl<R: Runtime> DataManager<R> {
     pub fn new(app: AppHandle<R>) -> Self {
+        let settings = settings::load_settings(&app);
+        let mut mirrors: Vec<String> = settings
+            .data_mirrors
+            .iter()
+            .map(|mirror| mirror.trim().trim_end_matches('/').to_string())
+            .filter(|mirror| !mirror.is_empty())
+            .collect();
+        if mirrors.is_empty() {
+            mirrors.push(settings::DEFAULT_DATA_MIRROR.to_string());
+        }
+
         Self {
             app,
-            client: Client::new(),
+            client: http::shared_client(&settings),
+            retry_policy: RetryPolicy::from_settings(&settings),
+            mirrors,
+            locale: settings.locale,
+            keep_versions: settings.keep_versions,
+            concurrency: settings.download_concurrency,
+            cancel_token: None,
+            report_progress: false,
         }
     }
 
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel_token = Some(token);
+        self
+    }
+
+    // Emits data-update-progress events, set by run_update
+    pub fn with_progress(mut self) -> Self {
+        self.report_progress = true;
+        self
+    }
+
+    // Races a request future against the cancellation token, if there is one
+    async fn cancellable<F: Future>(&self, future: F) -> Result<F::Output, AppError> {
+        match &self.cancel_token {
+            Some(token) => tokio::select! {
+                _ = token.cancelled() => Err(AppError::Cancelled),
+                output = future => Ok(output),
+            },
+            None => Ok(future.await),
+        }
+    }
+
+    // Walks the mirror list, moving on to the next one once a mirror has used up its retries
+    async fn with_mirrors<T, F, Fut>(&self, path: &str, mut fetch: F) -> Result<T, AppError>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<T, String>>,
+    {
+        let mut errors = Vec::new();
+        for mirror in &self.mirrors {
+            match fetch(format!("{}/{}", mirror, path)).await {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    println!("Mirror {} failed for {}: {}", mirror, path, e);
+                    errors.push(format!("{}: {}", mirror, e));
+                }
+            }
+        }
+        Err(AppError::Network(errors.join("; ")))
+    }
+
+    // Every Data Dragon request goes through here to get mirrors, retries and cancellation
+    async fn fetch_text(&self, path: &str) -> Result<String, AppError> {
+        let request = self.with_mirrors(path, |url| async move {
+            http::with_retry(&self.retry_policy, || async {
+                Ok(http::get(&self.client, &url).await?.text().await?)
+            })
+            .await
+        });
+        self.cancellable(request).await?
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    // Overrides the locale from the settings, used while switching locales
+    pub fn with_locale(mut self, locale: String) -> Self {
+        self.locale = locale;
+        self
+    }
+
     fn get_data_dir(&self) -> PathBuf {
-        let path = self.app.path().app_data_dir().unwrap();
-        path.join("league-data")
+        league_data_dir(&self.app)
     }
 
     fn get_version_dir(&self, version: &str) -> PathBuf {
         self.get_data_dir().join("versions").join(version)
     }
 
-    pub async fn fetch_latest_version(&self) -> Result<String, String> {
-        let versions: Vec<String> = self
-            .client
-            .get(VERSIONS_URL)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?
-            .json()
-            .await
-            .map_err(|e| e.to_string())?;
+    fn get_staging_dir(&self) -> PathBuf {
+        self.get_data_dir().join("staging")
+    }
+
+    fn get_locale_dir(&self, version: &str) -> PathBuf {
+        self.get_version_dir(version).join(&self.locale)
+    }
+
+    pub fn has_locale_data(&self, version: &str, locale: &str) -> bool {
+        let dir = self.get_version_dir(version).join(locale);
+        DATA_FILES
+            .iter()
+            .all(|file_name| dir.join(file_name).exists())
+    }
+
+    // Older installs kept en_US files directly in versions/{version}, move them into their locale dir.
+    // Only runs from recover() at startup, every other path just reads the layout.
+    fn migrate_legacy_layout(&self, version: &str) -> Result<(), AppError> {
+        let version_dir = self.get_version_dir(version);
+        if !version_dir.join("champion.json").exists() {
+            return Ok(());
+        }
+
+        let legacy_dir = version_dir.join("en_US");
+        fs::create_dir_all(&legacy_dir)?;
+        for entry in DATA_FILES.iter().copied().chain(["champion"]) {
+            let from = version_dir.join(entry);
+            if from.exists() {
+                fs::rename(&from, legacy_dir.join(entry))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // languages.json is cached so the locale picker keeps working offline
+    pub async fn fetch_locales(&self) -> Result<Vec<String>, AppError> {
+        let cache_path = self.get_data_dir().join("languages.json");
+
+        let content = match self.fetch_text(LANGUAGES_PATH).await.ok() {
+            Some(content) if serde_json::from_str::<Vec<String>>(&content).is_ok() => {
+                fs::create_dir_all(self.get_data_dir())?;
+                fs::write(&cache_path, &content)?;
+                content
+            }
+            _ => fs::read_to_string(&cache_path).map_err(|_| {
+                AppError::Network("Failed to fetch locales and no cached list found".to_string())
+            })?,
+        };
+
+        serde_json::from_str(&content).map_err(|e| AppError::Parse(format!("Locale Parse: {}", e)))
+    }
+
+    pub async fn list_locales(&self) -> Result<Vec<LocaleInfo>, AppError> {
+        let version = self.get_local_version();
+        let locales = self.fetch_locales().await?;
+        Ok(locales
+            .into_iter()
+            .map(|locale| {
+                let is_downloaded = version
+                    .as_deref()
+                    .is_some_and(|v| self.has_locale_data(v, &locale));
+                LocaleInfo {
+                    locale,
+                    is_downloaded,
+                }
+            })
+            .collect())
+    }
+
+    // Every version Data Dragon knows about, newest first
+    pub async fn fetch_versions(&self) -> Result<Vec<String>, AppError> {
+        Ok(self.fetch_versions_list().await?.versions)
+    }
+
+    // Sends the cached ETag/Last-Modified so an unchanged list costs a 304,
+    // and falls back to the cached list (marked stale) when Data Dragon can't be reached
+    pub async fn fetch_versions_list(&self) -> Result<VersionsList, AppError> {
+        let cache_path = self.get_data_dir().join(VERSIONS_CACHE_FILE);
+        let cache: Option<VersionsCache> = fs::read_to_string(&cache_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+        let validators = cache
+            .as_ref()
+            .map(|cache| cache.validators.clone())
+            .unwrap_or_default();
+
+        let request = self.with_mirrors(VERSIONS_PATH, |url| {
+            let validators = &validators;
+            async move {
+                http::with_retry(&self.retry_policy, || {
+                    http::get_conditional(&self.client, &url, validators)
+                })
+                .await
+            }
+        });
+        let response = self.cancellable(request).await?;
+
+        let checked_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let fresh = match (response, cache) {
+            (Ok(Conditional::Modified { body, validators }), _) => {
+                let versions: Vec<String> = serde_json::from_str(&body)
+                    .map_err(|e| AppError::Parse(format!("Versions Parse: {}", e)))?;
+                VersionsCache {
+                    versions,
+                    validators,
+                    checked_at,
+                }
+            }
+            (Ok(Conditional::NotModified), Some(cache)) => VersionsCache {
+                checked_at,
+                ..cache
+            },
+            (Ok(Conditional::NotModified), None) => {
+                return Err(AppError::Network(
+                    "Got 304 for versions.json without a cached copy".to_string(),
+                ))
+            }
+            (Err(e), Some(cache)) => {
+                println!("Using cached versions list, Data Dragon unreachable: {}", e);
+                return Ok(VersionsList {
+                    versions: cache.versions,
+                    is_stale: true,
+                    checked_at: Some(cache.checked_at),
+                });
+            }
+            (Err(e), None) => return Err(e),
+        };
+
+        fs::create_dir_all(self.get_data_dir())?;
+        let json = serde_json::to_vec_pretty(&fresh)?;
+        write_atomic(&cache_path, &json)?;
+
+        Ok(VersionsList {
+            versions: fresh.versions,
+            is_stale: false,
+            checked_at: Some(fresh.checked_at),
+        })
+    }
+
+    pub async fn fetch_latest_version(&self) -> Result<String, AppError> {
+        let versions = self.fetch_versions().await?;
 
         versions
             .first()
             .cloned()
-            .ok_or_else(|| "No versions found".to_string())
+            .ok_or_else(|| AppError::NotFound("No versions found".to_string()))
     }
 
     pub fn get_local_version(&self) -> Option<String> {
@@ -67,77 +406,762 @@ impl<R: Runtime> DataManager<R> {
         }
     }
 
-    pub async fn check_status(&self) -> Result<DataStatus, String> {
-        let latest = self.fetch_latest_version().await?;
+    pub async fn check_status(&self) -> Result<DataStatus, AppError> {
+        let list = self.fetch_versions_list().await?;
+        let latest = list
+            .versions
+            .first()
+            .cloned()
+            .ok_or_else(|| AppError::NotFound("No versions found".to_string()))?;
         let current = self.get_local_version();
+        let corrupt_files = current
+            .as_deref()
+            .map(|version| self.find_corrupt_files(version))
+            .unwrap_or_default();
+        // Installs from before runes and summoner spells only have some of DATA_FILES and
+        // can't load, reporting them as outdated makes the UI offer a full download
+        let is_complete = current
+            .as_deref()
+            .is_some_and(|version| self.has_locale_data(version, &self.locale));
         Ok(DataStatus {
-            is_up_to_date: current.as_deref() == Some(&latest),
+            is_up_to_date: is_complete && current.as_deref() == Some(&latest),
             current_version: current,
             latest_version: latest,
+            corrupt_files,
+            is_stale: list.is_stale,
+            checked_at: list.checked_at,
         })
     }
 
-    // Downloads champion.json and item.json for the specific version
-    pub async fn update_data(&self, version: String) -> Result<(), String> {
-        let version_dir = self.get_version_dir(&version);
-        if !version_dir.exists() {
-            fs::create_dir_all(&version_dir).map_err(|e| e.to_string())?;
+    pub fn find_corrupt_files(&self, version: &str) -> Vec<String> {
+        let dir = self.get_locale_dir(version);
+        Manifest::load(&dir)
+            .map(|manifest| manifest.verify(&dir))
+            .unwrap_or_default()
+    }
+
+    async fn fetch_data_file(&self, version: &str, file_name: &str) -> Result<String, AppError> {
+        let path = format!("cdn/{}/data/{}/{}", version, self.locale, file_name);
+        let body = self.fetch_bytes(&path, file_name).await?;
+
+        String::from_utf8(body).map_err(AppError::from)
+    }
+
+    async fn fetch_bytes(&self, path: &str, label: &str) -> Result<Vec<u8>, AppError> {
+        let request = self.with_mirrors(path, |url| async move {
+            http::with_retry(&self.retry_policy, || self.download(&url, label)).await
+        });
+        self.cancellable(request).await?.map_err(|e| match e {
+            AppError::Network(message) => {
+                AppError::Network(format!("Failed to fetch {}: {}", label, message))
+            }
+            e => e,
+        })
+    }
+
+    // Downloads the images missing from versions/{version}/img, already cached files are skipped
+    pub async fn download_images(
+        &self,
+        version: &str,
+        requests: Vec<ImageRequest>,
+    ) -> Result<DownloadReport, AppError> {
+        let img_dir = self.get_version_dir(version).join(IMAGE_DIR);
+        let mut report = DownloadReport::default();
+
+        let mut cdn_paths = HashMap::new();
+        for request in requests {
+            if img_dir.join(&request.relative_path).exists() {
+                report.skipped.push(request.relative_path);
+            } else {
+                cdn_paths.insert(request.relative_path, request.cdn_path);
+            }
         }
 
-        let champ_url = format!(
-            "{}/{}/data/{}/champion.json",
-            BASE_CDN_URL, version, LANG_CODE
+        let names: Vec<String> = cdn_paths.keys().cloned().collect();
+        let results = download::run_bounded(self.concurrency, names, |relative_path| {
+            let cdn_path = &cdn_paths[&relative_path];
+            let target = img_dir.join(&relative_path);
+            async move {
+                let content = self.fetch_bytes(cdn_path, &relative_path).await?;
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                write_atomic(&target, &content)
+            }
+        })
+        .await;
+
+        // Cancellation aborts the whole run, anything else only skips that image
+        self.check_cancelled()?;
+        for (relative_path, result) in &results {
+            report.record(relative_path, result);
+        }
+        Ok(report)
+    }
+
+    // One download attempt, streamed so an update can report progress while it runs
+    async fn download(&self, url: &str, file_name: &str) -> Result<Vec<u8>, RequestError> {
+        let mut response = http::get(&self.client, url).await?;
+
+        // Content-Length may be missing, the frontend then shows an indeterminate bar
+        let total = response.content_length();
+        let mut body = Vec::with_capacity(total.unwrap_or(0) as usize);
+        self.emit_progress(UpdatePhase::Fetching, Some(file_name), 0, total);
+
+        let mut last_emit = Instant::now();
+        while let Some(chunk) = response.chunk().await? {
+            body.extend_from_slice(&chunk);
+            if last_emit.elapsed() >= PROGRESS_INTERVAL {
+                last_emit = Instant::now();
+                self.emit_progress(
+                    UpdatePhase::Fetching,
+                    Some(file_name),
+                    body.len() as u64,
+                    total,
+                );
+            }
+        }
+        self.emit_progress(
+            UpdatePhase::Fetching,
+            Some(file_name),
+            body.len() as u64,
+            total,
         );
-        let champ_data = self
-            .client
-            .get(&champ_url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch champions: {}", e))?
-            .text()
-            .await
-            .map_err(|e| e.to_string())?;
 
-        fs::write(version_dir.join("champion.json"), &champ_data).map_err(|e| e.to_string())?;
+        Ok(body)
+    }
 
-        let item_url = format!("{}/{}/data/{}/item.json", BASE_CDN_URL, version, LANG_CODE);
-        let item_data = self
-            .client
-            .get(&item_url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch items: {}", e))?
-            .text()
+    pub fn emit_progress(
+        &self,
+        phase: UpdatePhase,
+        file: Option<&str>,
+        downloaded: u64,
+        total: Option<u64>,
+    ) {
+        if !self.report_progress {
+            return;
+        }
+        let progress = UpdateProgress {
+            phase,
+            file: file.map(|f| f.to_string()),
+            downloaded,
+            total,
+        };
+        let _ = self.app.emit(PROGRESS_EVENT, progress);
+    }
+
+    pub fn emit_complete(&self, result: &Result<UpdateReport, AppError>) {
+        let complete = UpdateComplete {
+            version: result.as_ref().ok().map(|report| report.version.clone()),
+            success: result.is_ok(),
+            error: result.as_ref().err().cloned(),
+            report: result.as_ref().ok().map(|report| report.files.clone()),
+        };
+        let _ = self.app.emit(COMPLETE_EVENT, complete);
+    }
+
+    // Downloads the version and makes it the active one
+    pub async fn update_data(&self, version: String) -> Result<DownloadReport, AppError> {
+        let report = self.download_version(&version).await?;
+
+        // Only point metadata.json at the new version once its data is in place
+        self.set_active_version(&version)?;
+        Ok(report)
+    }
+
+    // Downloads every file in DATA_FILES for the specific version in the active locale.
+    // Files are staged and validated first so a failed update never touches the live data.
+    pub async fn download_version(&self, version: &str) -> Result<DownloadReport, AppError> {
+        let staging_dir = self.get_staging_dir().join(Uuid::new_v4().to_string());
+        fs::create_dir_all(&staging_dir)?;
+
+        let result = self
+            .stage_data(version, &staging_dir)
             .await
-            .map_err(|e| e.to_string())?;
+            .and_then(|report| {
+                self.check_cancelled()?;
+                self.commit_staged(version, &self.locale, &staging_dir)?;
+                Ok(report)
+            });
+        if staging_dir.exists() {
+            let _ = fs::remove_dir_all(&staging_dir);
+        }
+        result
+    }
 
-        fs::write(version_dir.join("item.json"), &item_data).map_err(|e| e.to_string())?;
+    pub fn set_active_version(&self, version: &str) -> Result<(), AppError> {
+        if !self.has_locale_data(version, &self.locale) {
+            return Err(AppError::NotFound(format!(
+                "Version {} is not downloaded for {}",
+                version, self.locale
+            )));
+        }
 
         let metadata = serde_json::json!({ "version": version });
-        fs::write(
-            self.get_data_dir().join("metadata.json"),
-            serde_json::to_string_pretty(&metadata).unwrap(),
+        write_atomic(
+            &self.get_data_dir().join("metadata.json"),
+            serde_json::to_string_pretty(&metadata).unwrap().as_bytes(),
         )
-        .map_err(|e| e.to_string())?;
+    }
+
+    // Every version directory on disk, newest first. Read-only, so it is safe outside the write lock.
+    pub fn list_local_versions(&self) -> Result<Vec<LocalVersion>, AppError> {
+        let versions_dir = self.get_data_dir().join("versions");
+        if !versions_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let active = self.get_local_version();
+        let mut versions = Vec::new();
+        for entry in fs::read_dir(&versions_dir)? {
+            let entry = entry?;
+            if !entry.path().is_dir() {
+                continue;
+            }
+            let version = entry.file_name().to_string_lossy().to_string();
+
+            let mut locales: Vec<String> = fs::read_dir(entry.path())?
+                .filter_map(|locale| locale.ok())
+                .map(|locale| locale.file_name().to_string_lossy().to_string())
+                .filter(|locale| !locale.ends_with(PARKED_SUFFIX))
+                .filter(|locale| self.has_locale_data(&version, locale))
+                .collect();
+            locales.sort();
+
+            versions.push(LocalVersion {
+                is_active: active.as_deref() == Some(version.as_str()),
+                version,
+                locales,
+            });
+        }
+
+        versions.sort_by(|a, b| compare_versions(&b.version, &a.version));
+        Ok(versions)
+    }
+
+    // Keeps the newest keep_versions versions plus the active one, returns what was removed
+    pub fn prune_versions(&self) -> Result<Vec<String>, AppError> {
+        let mut removed = Vec::new();
+        for local in self
+            .list_local_versions()?
+            .into_iter()
+            .skip(self.keep_versions.max(1))
+        {
+            if local.is_active {
+                continue;
+            }
+            fs::remove_dir_all(self.get_version_dir(&local.version))?;
+            removed.push(local.version);
+        }
+
+        Ok(removed)
+    }
+
+    fn check_cancelled(&self) -> Result<(), AppError> {
+        match &self.cancel_token {
+            Some(token) if token.is_cancelled() => Err(AppError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    async fn stage_data(
+        &self,
+        version: &str,
+        staging_dir: &Path,
+    ) -> Result<DownloadReport, AppError> {
+        let names: Vec<String> = DATA_FILES
+            .iter()
+            .map(|file_name| file_name.to_string())
+            .collect();
+        let results = download::run_bounded(self.concurrency, names, |file_name| async move {
+            let content = self.fetch_data_file(version, &file_name).await?;
+            fs::write(staging_dir.join(&file_name), &content)?;
+            Ok::<_, AppError>(content)
+        })
+        .await;
+        self.check_cancelled()?;
+
+        let mut report = DownloadReport::default();
+        let mut manifest = Manifest::default();
+        for (file_name, result) in &results {
+            report.record(file_name, result);
+            if let Ok(content) = result {
+                manifest.record(file_name, content.as_bytes());
+            }
+        }
+        // Every data file is needed, one failure fails the whole update
+        if !report.failed.is_empty() {
+            return Err(AppError::Network(format!(
+                "Failed to download: {}",
+                report.failure_summary()
+            )));
+        }
+
+        self.emit_progress(UpdatePhase::Validating, None, 0, None);
+        parse_data_dir(staging_dir)?;
+        manifest.save(staging_dir)?;
+        Ok(report)
+    }
+
+    // Swaps the staged directory in with renames, which are atomic within league-data
+    fn commit_staged(
+        &self,
+        version: &str,
+        locale: &str,
+        staging_dir: &Path,
+    ) -> Result<(), AppError> {
+        let locale_dir = self.get_version_dir(version).join(locale);
+        fs::create_dir_all(self.get_version_dir(version))?;
+
+        if !locale_dir.exists() {
+            return fs::rename(staging_dir, &locale_dir).map_err(AppError::from);
+        }
+
+        // Detail files of the same version stay valid, carry them and their manifest entries over
+        let detail_dir = locale_dir.join("champion");
+        if detail_dir.exists() && !staging_dir.join("champion").exists() {
+            fs::rename(&detail_dir, staging_dir.join("champion"))?;
+
+            if let Some(old_manifest) = Manifest::load(&locale_dir) {
+                let mut manifest = Manifest::load(staging_dir).unwrap_or_default();
+                manifest.files.extend(
+                    old_manifest
+                        .files
+                        .into_iter()
+                        .filter(|(name, _)| name.starts_with("champion/")),
+                );
+                manifest.save(staging_dir)?;
+            }
+        }
+
+        // Parked next to its replacement so recover() can put it back after a crash mid-swap
+        let old_dir = self
+            .get_version_dir(version)
+            .join(format!("{}{}", locale, PARKED_SUFFIX));
+        if old_dir.exists() {
+            fs::remove_dir_all(&old_dir)?;
+        }
+        fs::rename(&locale_dir, &old_dir)?;
+        if let Err(e) = fs::rename(staging_dir, &locale_dir) {
+            let _ = fs::rename(&old_dir, &locale_dir);
+            return Err(e.into());
+        }
+        let _ = fs::remove_dir_all(&old_dir);
+
+        Ok(())
+    }
+
+    // Imports a dragontail-{version}.tgz or a zip exported by another install.
+    // Everything is extracted and validated before the first locale is swapped in.
+    pub fn import_archive(&self, archive_path: &Path) -> Result<Vec<ImportedData>, AppError> {
+        let staging_dir = self.get_staging_dir().join(Uuid::new_v4().to_string());
+        fs::create_dir_all(&staging_dir)?;
+
+        let result = self.import_staged(archive_path, &staging_dir);
+        if staging_dir.exists() {
+            let _ = fs::remove_dir_all(&staging_dir);
+        }
+        result
+    }
+
+    fn import_staged(
+        &self,
+        archive_path: &Path,
+        staging_dir: &Path,
+    ) -> Result<Vec<ImportedData>, AppError> {
+        archive::extract(archive_path, staging_dir)?;
+
+        // Extraction lays data out as {version}/{locale}/, locales missing a file are skipped
+        let mut staged = Vec::new();
+        for version_entry in fs::read_dir(staging_dir)? {
+            let version_dir = version_entry?.path();
+            for locale_entry in fs::read_dir(&version_dir)? {
+                let locale_dir = locale_entry?.path();
+                if !DATA_FILES
+                    .iter()
+                    .all(|file_name| locale_dir.join(file_name).exists())
+                {
+                    continue;
+                }
+
+                let imported = ImportedData {
+                    version: file_name_of(&version_dir),
+                    locale: file_name_of(&locale_dir),
+                };
+                validate_imported(&locale_dir).map_err(|e| {
+                    AppError::InvalidInput(format!(
+                        "{} ({}): {}",
+                        imported.version, imported.locale, e
+                    ))
+                })?;
+                staged.push((imported, locale_dir));
+            }
+        }
+
+        if staged.is_empty() {
+            return Err(AppError::InvalidInput(
+                "No complete Data Dragon data found in the archive".to_string(),
+            ));
+        }
+
+        // Last chance to cancel, once locales start being swapped in the import runs to the end
+        self.check_cancelled()?;
+        for (imported, locale_dir) in &staged {
+            self.commit_staged(&imported.version, &imported.locale, locale_dir)?;
+        }
+        Ok(staged.into_iter().map(|(imported, _)| imported).collect())
+    }
+
+    // Packs every locale of the active version into a zip that import_archive understands
+    pub fn export_archive(&self, dest: &Path) -> Result<ExportInfo, AppError> {
+        let version = self
+            .get_local_version()
+            .ok_or_else(AppError::no_local_data)?;
+        let version_dir = self.get_version_dir(&version);
+
+        // Never hand a broken snapshot to a teammate
+        for entry in fs::read_dir(&version_dir)? {
+            let locale_dir = entry?.path();
+            if let Some(manifest) = Manifest::load(&locale_dir) {
+                let corrupt_files = manifest.verify(&locale_dir);
+                if !corrupt_files.is_empty() {
+                    let locale = file_name_of(&locale_dir);
+                    return Err(AppError::Corrupt(
+                        corrupt_files
+                            .iter()
+                            .map(|file_name| format!("{}/{}", locale, file_name))
+                            .collect(),
+                    ));
+                }
+            }
+        }
+
+        archive::export(&version_dir, &version, dest)
+    }
+
+    // Startup pass: anything left in staging or half-written .tmp files come from an interrupted update
+    pub fn recover(&self) -> Result<(), AppError> {
+        self.restore_version_dirs()?;
+
+        let staging_dir = self.get_staging_dir();
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir)?;
+        }
+
+        let metadata_tmp = self.get_data_dir().join("metadata.json.tmp");
+        if metadata_tmp.exists() {
+            fs::remove_file(&metadata_tmp)?;
+        }
+
+        Ok(())
+    }
+
+    // A parked locale whose replacement never made it into place is moved back, otherwise
+    // the swap finished and the parked copy is just left over. Legacy layouts are migrated
+    // here too, before any command can read the versions directory.
+    fn restore_version_dirs(&self) -> Result<(), AppError> {
+        let versions_dir = self.get_data_dir().join("versions");
+        if !versions_dir.exists() {
+            return Ok(());
+        }
+
+        for version_entry in fs::read_dir(&versions_dir)? {
+            let version_dir = version_entry?.path();
+            if !version_dir.is_dir() {
+                continue;
+            }
+            self.migrate_legacy_layout(&file_name_of(&version_dir))?;
+
+            for entry in fs::read_dir(&version_dir)? {
+                let parked = entry?.path();
+                let Some(locale) = file_name_of(&parked)
+                    .strip_suffix(PARKED_SUFFIX)
+                    .map(str::to_string)
+                else {
+                    continue;
+                };
+
+                let locale_dir = version_dir.join(&locale);
+                if locale_dir.exists() {
+                    fs::remove_dir_all(&parked)?;
+                } else {
+                    println!(
+                        "Restoring {} from an interrupted update",
+                        locale_dir.display()
+                    );
+                    fs::rename(&parked, &locale_dir)?;
+                }
+            }
+        }
 
         Ok(())
     }
 
-    pub fn load_data(&self) -> Result<(ChampionData, ItemData), String> {
+    pub fn load_data(&self) -> Result<LeagueDataState, AppError> {
+        let version = self
+            .get_local_version()
+            .ok_or_else(AppError::no_local_data)?;
+        self.load_version(&version)
+    }
+
+    // Loads any local version in the active locale, not just the active one
+    pub fn load_version(&self, version: &str) -> Result<LeagueDataState, AppError> {
+        let dir = self.get_locale_dir(version);
+        let (loaded, mut manifest) = self.read_locale_dir(version)?;
+
+        // Directories from before manifests get one once their files parse
+        if DATA_FILES
+            .iter()
+            .any(|file_name| !manifest.files.contains_key(*file_name))
+        {
+            for file_name in DATA_FILES {
+                let content = fs::read(dir.join(file_name))?;
+                manifest.record(file_name, &content);
+            }
+            manifest.save(&dir)?;
+        }
+
+        Ok(loaded)
+    }
+
+    // Read-only load for comparisons: the version must be one listed locally, and nothing is
+    // migrated or written, so a version string from the frontend can't reach outside versions/
+    pub fn read_version(&self, version: &str) -> Result<LeagueDataState, AppError> {
+        let is_local = self
+            .list_local_versions()?
+            .iter()
+            .any(|local| local.version == version && local.locales.contains(&self.locale));
+        if !is_local {
+            return Err(AppError::NotFound(format!(
+                "Version {} is not downloaded for {}",
+                version, self.locale
+            )));
+        }
+
+        self.read_locale_dir(version).map(|(loaded, _)| loaded)
+    }
+
+    fn read_locale_dir(&self, version: &str) -> Result<(LeagueDataState, Manifest), AppError> {
+        let dir = self.get_locale_dir(version);
+        if !self.has_locale_data(version, &self.locale) {
+            return Err(AppError::NotFound(format!(
+                "Version {} is not downloaded for {}",
+                version, self.locale
+            )));
+        }
+
+        // Only the summary files block a load. Cached champion/{id}.json files that fail their
+        // check are fetched again by load_champion_detail the next time they are opened.
+        let manifest = Manifest::load(&dir).unwrap_or_default();
+        let (corrupt_files, corrupt_details): (Vec<String>, Vec<String>) = manifest
+            .verify(&dir)
+            .into_iter()
+            .partition(|file_name| DATA_FILES.contains(&file_name.as_str()));
+        if !corrupt_files.is_empty() {
+            return Err(AppError::Corrupt(corrupt_files));
+        }
+        if !corrupt_details.is_empty() {
+            println!(
+                "Cached champion details will be fetched again: {:?}",
+                corrupt_details
+            );
+        }
+
+        let loaded = parse_data_dir(&dir)?;
+        if let Some(validation) = loaded.validation.as_ref().filter(|v| !v.is_clean()) {
+            let (unknown, missing) = validation.files.iter().fold((0, 0), |(u, m), file| {
+                (u + file.unknown_fields.len(), m + file.missing_fields.len())
+            });
+            println!(
+                "Schema drift in {} ({}): {} unknown and {} missing fields",
+                version, self.locale, unknown, missing
+            );
+        }
+
+        Ok((loaded, manifest))
+    }
+
+    // Re-downloads only the files that fail their manifest check, files that still fail are reported
+    pub async fn repair_data(&self) -> Result<DownloadReport, AppError> {
         let version = self
             .get_local_version()
-            .ok_or("No local data found. Please update.")?;
-        let dir = self.get_version_dir(&version);
+            .ok_or_else(AppError::no_local_data)?;
+        let dir = self.get_locale_dir(&version);
+        let mut manifest = Manifest::load(&dir).ok_or_else(|| {
+            AppError::NotLoaded("No manifest found for local data. Please update.".to_string())
+        })?;
 
-        let champ_content =
-            fs::read_to_string(dir.join("champion.json")).map_err(|e| e.to_string())?;
-        let champ_data: ChampionData =
-            serde_json::from_str(&champ_content).map_err(|e| format!("Champ Parse: {}", e))?;
+        let corrupt_files = manifest.verify(&dir);
+        let results = download::run_bounded(self.concurrency, corrupt_files, |file_name| {
+            let (version, dir) = (&version, &dir);
+            async move {
+                let content = self.fetch_data_file(version, &file_name).await?;
+                validate_file(&file_name, &content)?;
 
-        let item_content = fs::read_to_string(dir.join("item.json")).map_err(|e| e.to_string())?;
-        let item_data: ItemData =
-            serde_json::from_str(&item_content).map_err(|e| format!("Item Parse: {}", e))?;
+                let path = dir.join(&file_name);
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                write_atomic(&path, content.as_bytes())?;
+                Ok::<_, AppError>(content)
+            }
+        })
+        .await;
+        self.check_cancelled()?;
 
-        Ok((champ_data, item_data))
+        let mut report = DownloadReport::default();
+        for (file_name, result) in &results {
+            report.record(file_name, result);
+            if let Ok(content) = result {
+                manifest.record(file_name, content.as_bytes());
+            }
+        }
+        manifest.save(&dir)?;
+
+        Ok(report)
     }
+
+    // champion/{id}.json is cached next to the summary files the first time it is requested
+    pub async fn load_champion_detail(&self, id: &str) -> Result<ChampionDetail, AppError> {
+        let version = self
+            .get_local_version()
+            .ok_or_else(AppError::no_local_data)?;
+        let dir = self.get_locale_dir(&version);
+        let file_name = format!("champion/{}.json", id);
+        let detail_path = dir.join(&file_name);
+        let mut manifest = Manifest::load(&dir).unwrap_or_default();
+
+        // A cached copy that no longer matches its manifest entry is fetched again
+        let cached = fs::read_to_string(&detail_path).ok().filter(|content| {
+            !manifest.files.contains_key(&file_name)
+                || manifest.matches(&file_name, content.as_bytes())
+        });
+        let content = match cached {
+            Some(content) => content,
+            None => {
+                let content = self.fetch_data_file(&version, &file_name).await?;
+                if let Some(parent) = detail_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                write_atomic(&detail_path, content.as_bytes())?;
+                manifest.record(&file_name, content.as_bytes());
+                manifest.save(&dir)?;
+                content
+            }
+        };
+
+        let mut detail_data: ChampionDetailData = serde_json::from_str(&content)
+            .map_err(|e| AppError::Parse(format!("Champ Detail Parse: {}", e)))?;
+        detail_data.data.remove(id).ok_or_else(|| {
+            AppError::NotFound(format!("Champion {} not found in its detail file", id))
+        })
+    }
+}
+
+// league-data inside the app data dir, shared with the asset protocol which has no DataManager
+pub fn league_data_dir<R: Runtime>(app: &AppHandle<R>) -> PathBuf {
+    let path = app.path().app_data_dir().unwrap();
+    path.join("league-data")
+}
+
+// Parses every file in DATA_FILES, used both to validate staged downloads and to load live data
+// Parsing is lenient, schema drift is collected into the state's validation report
+fn parse_data_dir(dir: &Path) -> Result<LeagueDataState, AppError> {
+    let mut validation = ValidationReport::default();
+
+    let champ_content = fs::read_to_string(dir.join("champion.json"))?;
+    let (champ_data, report) =
+        schema::parse_checked::<ChampionData>("champion.json", &champ_content)?;
+    validation.files.push(report);
+
+    let item_content = fs::read_to_string(dir.join("item.json"))?;
+    let (item_data, report) = schema::parse_checked::<ItemData>("item.json", &item_content)?;
+    validation.files.push(report);
+
+    let rune_content = fs::read_to_string(dir.join("runesReforged.json"))?;
+    let (rune_data, report) =
+        schema::parse_checked::<Vec<RunePath>>("runesReforged.json", &rune_content)?;
+    validation.files.push(report);
+
+    let summoner_content = fs::read_to_string(dir.join("summoner.json"))?;
+    let (summoner_data, report) =
+        schema::parse_checked::<SummonerData>("summoner.json", &summoner_content)?;
+    validation.files.push(report);
+
+    Ok(LeagueDataState {
+        champions: Some(champ_data),
+        items: Some(item_data),
+        runes: Some(rune_data),
+        summoner_spells: Some(summoner_data),
+        champion_details: HashMap::new(),
+        validation: Some(validation),
+    })
+}
+
+// Orders "14.10.1" after "14.9.1", falling back to text comparison for odd entries like "lolpatch_3.7"
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |v: &str| -> Option<Vec<u32>> { v.split('.').map(|p| p.parse().ok()).collect() };
+    match (parts(a), parts(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => a.cmp(b),
+    }
+}
+
+// Archives from other installs carry a manifest that has to match, dragontail data gets a fresh one
+fn validate_imported(dir: &Path) -> Result<(), AppError> {
+    let manifest = match Manifest::load(dir) {
+        Some(manifest) => {
+            let corrupt_files = manifest.verify(dir);
+            if !corrupt_files.is_empty() {
+                return Err(AppError::Corrupt(corrupt_files));
+            }
+            manifest
+        }
+        None => {
+            let mut manifest = Manifest::default();
+            let detail_files = fs::read_dir(dir.join("champion"))
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| entry.ok())
+                .map(|entry| format!("champion/{}", entry.file_name().to_string_lossy()));
+            for file_name in DATA_FILES.iter().map(|f| f.to_string()).chain(detail_files) {
+                let content = fs::read(dir.join(&file_name))?;
+                manifest.record(&file_name, &content);
+            }
+            manifest
+        }
+    };
+
+    parse_data_dir(dir)?;
+    manifest.save(dir)
+}
+
+fn file_name_of(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+// Checks a single re-downloaded file parses as what its name says it is
+fn validate_file(file_name: &str, content: &str) -> Result<(), AppError> {
+    let result = match file_name {
+        "champion.json" => serde_json::from_str::<ChampionData>(content).map(|_| ()),
+        "item.json" => serde_json::from_str::<ItemData>(content).map(|_| ()),
+        "runesReforged.json" => serde_json::from_str::<Vec<RunePath>>(content).map(|_| ()),
+        "summoner.json" => serde_json::from_str::<SummonerData>(content).map(|_| ()),
+        _ => serde_json::from_str::<ChampionDetailData>(content).map(|_| ()),
+    };
+    result.map_err(|e| AppError::Parse(format!("{} Parse: {}", file_name, e)))
+}
+
+// Writes next to the target and renames, so readers never see a partially written file
+pub(crate) fn write_atomic(path: &Path, content: &[u8]) -> Result<(), AppError> {
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path).map_err(AppError::from)
 }
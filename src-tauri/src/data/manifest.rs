@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::data::manager::write_atomic;
+use crate::error::AppError;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+// Size and SHA-256 of every file in a version data directory, recorded at download time
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Manifest {
+    // Keyed by path relative to the data directory, e.g. "item.json" or "champion/Ahri.json"
+    pub files: BTreeMap<String, FileEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileEntry {
+    pub size: u64,
+    pub sha256: String,
+}
+
+impl FileEntry {
+    pub fn from_content(content: &[u8]) -> Self {
+        Self {
+            size: content.len() as u64,
+            sha256: format!("{:x}", Sha256::digest(content)),
+        }
+    }
+}
+
+impl Manifest {
+    // None when the directory predates manifests
+    pub fn load(dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(dir.join(MANIFEST_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), AppError> {
+        let json = serde_json::to_string_pretty(self)?;
+        write_atomic(&dir.join(MANIFEST_FILE), json.as_bytes())
+    }
+
+    pub fn record(&mut self, name: &str, content: &[u8]) {
+        self.files
+            .insert(name.to_string(), FileEntry::from_content(content));
+    }
+
+    pub fn matches(&self, name: &str, content: &[u8]) -> bool {
+        self.files
+            .get(name)
+            .is_some_and(|entry| *entry == FileEntry::from_content(content))
+    }
+
+    // Returns the files that are missing or no longer match their recorded size and hash
+    pub fn verify(&self, dir: &Path) -> Vec<String> {
+        self.files
+            .iter()
+            .filter(|(name, entry)| match fs::read(dir.join(name)) {
+                // Size is checked first so truncated files skip hashing
+                Ok(content) => {
+                    content.len() as u64 != entry.size
+                        || FileEntry::from_content(&content) != **entry
+                }
+                Err(_) => true,
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
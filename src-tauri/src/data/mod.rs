@@ -1,17 +1,48 @@
+pub mod archive;
+pub mod assets;
 pub mod champion;
+pub mod compare;
+pub mod diff;
+pub mod download;
+pub mod dps;
+pub mod durability;
+pub mod http;
 pub mod item;
 pub mod manager;
+pub mod manifest;
+pub mod rune;
+pub mod schema;
+pub mod search;
+pub mod summoner;
+#[cfg(test)]
+mod test_util;
+pub mod updater;
 
 use crate::data::{
-    champion::{Champion, ChampionData},
-    item::ItemData,
-    manager::DataManager,
+    archive::ExportInfo,
+    champion::{Champion, ChampionData, ChampionDetail, LevelStats, MAX_LEVEL, MIN_LEVEL},
+    compare::{ChampionComparison, LevelRange},
+    diff::VersionDiff,
+    download::DownloadReport,
+    dps::AutoAttackDps,
+    durability::Durability,
+    item::{Item, ItemData},
+    manager::{DataManager, ImportedData, LocalVersion, LocaleInfo, UpdatePhase, UpdateReport},
+    rune::RunePath,
+    schema::ValidationReport,
+    search::{ChampionPage, ChampionQuery},
+    summoner::{SummonerData, SummonerSpell},
 };
+use crate::error::AppError;
+use crate::settings;
 use serde::{Deserialize, Serialize};
-use std::sync::Mutex;
+use std::{collections::HashMap, path::PathBuf, sync::Mutex};
 use tauri::{AppHandle, Runtime, State};
+use tokio_util::sync::CancellationToken;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// An empty image only breaks an icon, so every field falls back to a default
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct Image {
     pub full: String,
     pub sprite: String,
@@ -23,24 +54,65 @@ pub struct Image {
 }
 
 // In-memory state storage
+#[derive(Default)]
 pub struct LeagueDataState {
     pub champions: Option<ChampionData>,
     pub items: Option<ItemData>,
+    pub runes: Option<Vec<RunePath>>,
+    pub summoner_spells: Option<SummonerData>,
+    // Lazily filled by get_champion_detail, keyed by champion id
+    pub champion_details: HashMap<String, ChampionDetail>,
+    // Unknown and missing fields found while parsing the loaded files
+    pub validation: Option<ValidationReport>,
 }
 
-pub struct AppState(pub Mutex<LeagueDataState>);
+#[derive(Default)]
+pub struct AppState {
+    pub data: Mutex<LeagueDataState>,
+    // Set while any command writes under versions/ so cancel_data_update can abort it
+    pub update_token: Mutex<Option<CancellationToken>>,
+    // Last patch found by the background updater, kept for a UI that wasn't listening yet
+    pub pending_update: Mutex<Option<updater::UpdateAvailable>>,
+}
+
+// Held by every command that writes under versions/, so two writers never swap the same
+// locale directory at once. Released on drop, including when the command fails.
+pub struct DataWriteGuard<'a> {
+    state: &'a AppState,
+    token: CancellationToken,
+}
+
+impl AppState {
+    pub fn begin_write(&self) -> Result<DataWriteGuard<'_>, AppError> {
+        let mut update_token = self.update_token.lock()?;
+        if update_token.is_some() {
+            return Err(AppError::Busy);
+        }
+
+        let token = CancellationToken::new();
+        *update_token = Some(token.clone());
+        Ok(DataWriteGuard { state: self, token })
+    }
+}
+
+impl DataWriteGuard<'_> {
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+}
 
-impl Default for AppState {
-    fn default() -> Self {
-        Self(Mutex::new(LeagueDataState {
-            champions: None,
-            items: None,
-        }))
+impl Drop for DataWriteGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut update_token) = self.state.update_token.lock() {
+            *update_token = None;
+        }
     }
 }
 
 #[tauri::command]
-pub async fn get_data_status<R: Runtime>(app: AppHandle<R>) -> Result<manager::DataStatus, String> {
+pub async fn get_data_status<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<manager::DataStatus, AppError> {
     let manager = DataManager::new(app);
     manager.check_status().await
 }
@@ -49,37 +121,171 @@ pub async fn get_data_status<R: Runtime>(app: AppHandle<R>) -> Result<manager::D
 pub async fn update_data<R: Runtime>(
     app: AppHandle<R>,
     state: State<'_, AppState>,
-) -> Result<String, String> {
-    let manager = DataManager::new(app.clone());
+) -> Result<UpdateReport, AppError> {
+    run_update(app, &state).await
+}
+
+// Shared by update_data and the background updater so both respect the same single-update lock
+pub async fn run_update<R: Runtime>(
+    app: AppHandle<R>,
+    state: &AppState,
+) -> Result<UpdateReport, AppError> {
+    let guard = state.begin_write()?;
+    let manager = DataManager::new(app.clone())
+        .with_cancellation(guard.token())
+        .with_progress();
+
+    let result = download_latest(&manager, state).await;
+    manager.emit_complete(&result);
+
+    drop(guard);
+    if result.is_ok() {
+        if let Ok(mut pending) = state.pending_update.lock() {
+            *pending = None;
+        }
+    }
+    result
+}
+
+// Aborts a running update or other data write, staged files are discarded and the previous data stays active
+#[tauri::command]
+pub fn cancel_data_update(state: State<'_, AppState>) -> Result<bool, AppError> {
+    let update_token = state.update_token.lock()?;
 
+    match update_token.as_ref() {
+        Some(token) => {
+            token.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+async fn download_latest<R: Runtime>(
+    manager: &DataManager<R>,
+    state: &AppState,
+) -> Result<UpdateReport, AppError> {
     // Fetch latest version again to ensure we download the right one
     let latest_version = manager.fetch_latest_version().await?;
 
     // Download files to disk
-    manager.update_data(latest_version.clone()).await?;
+    let files = manager.update_data(latest_version.clone()).await?;
 
     // Load from disk into memory immediately
-    let (champs, items) = manager.load_data()?;
+    manager.emit_progress(UpdatePhase::Loading, None, 0, None);
+    let loaded = manager.load_data()?;
+
+    {
+        let mut data = state.data.lock()?;
+        *data = loaded;
+    }
 
-    let mut data = state.0.lock().map_err(|_| "Failed to lock state")?;
-    data.champions = Some(champs);
-    data.items = Some(items);
+    // Retention only runs here so a manually downloaded old patch survives until the next update
+    match manager.prune_versions() {
+        Ok(removed) if !removed.is_empty() => println!("Pruned old versions: {:?}", removed),
+        Ok(_) => {}
+        Err(e) => println!("Failed to prune old versions: {}", e),
+    }
+
+    Ok(UpdateReport {
+        version: latest_version,
+        files,
+    })
+}
 
-    Ok(latest_version)
+#[tauri::command]
+pub fn list_local_versions<R: Runtime>(app: AppHandle<R>) -> Result<Vec<LocalVersion>, AppError> {
+    let manager = DataManager::new(app);
+    manager.list_local_versions()
+}
+
+#[tauri::command]
+pub async fn get_available_versions<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<String>, AppError> {
+    let manager = DataManager::new(app);
+    manager.fetch_versions().await
+}
+
+// Downloads any version listed in versions.json without switching to it
+#[tauri::command]
+pub async fn download_version<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+    version: String,
+) -> Result<String, AppError> {
+    let guard = state.begin_write()?;
+    let manager = DataManager::new(app).with_cancellation(guard.token());
+
+    let versions = manager.fetch_versions().await?;
+    if !versions.contains(&version) {
+        return Err(AppError::NotFound(format!("Unknown version: {}", version)));
+    }
+
+    manager.download_version(&version).await?;
+    Ok(version)
+}
+
+#[tauri::command]
+pub fn set_active_version<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+    version: String,
+) -> Result<String, AppError> {
+    let _guard = state.begin_write()?;
+    let manager = DataManager::new(app);
+
+    let is_local = manager
+        .list_local_versions()?
+        .iter()
+        .any(|local| local.version == version);
+    if !is_local {
+        return Err(AppError::NotFound(format!(
+            "Version {} is not downloaded",
+            version
+        )));
+    }
+
+    // Loaded before metadata.json is rewritten, so a broken version never becomes the active one
+    let loaded = manager.load_version(&version)?;
+    manager.set_active_version(&version)?;
+
+    let mut data = state.data.lock()?;
+    *data = loaded;
+
+    Ok(version)
+}
+
+// Re-fetches only the files that failed their integrity check, then reloads them
+#[tauri::command]
+pub async fn repair_data<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+) -> Result<DownloadReport, AppError> {
+    let guard = state.begin_write()?;
+    let manager = DataManager::new(app).with_cancellation(guard.token());
+    let repaired = manager.repair_data().await?;
+
+    let loaded = manager.load_data()?;
+    let mut data = state.data.lock()?;
+    *data = loaded;
+
+    Ok(repaired)
 }
 
 #[tauri::command]
 pub async fn load_local_data<R: Runtime>(
     app: AppHandle<R>,
     state: State<'_, AppState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
+    // Loading backfills manifests for directories that predate them
+    let _guard = state.begin_write()?;
     let manager = DataManager::new(app);
 
     if manager.get_local_version().is_some() {
-        let (champs, items) = manager.load_data()?;
-        let mut data = state.0.lock().map_err(|_| "Failed to lock state")?;
-        data.champions = Some(champs);
-        data.items = Some(items);
+        let loaded = manager.load_data()?;
+        let mut data = state.data.lock()?;
+        *data = loaded;
         Ok("Loaded successfully".to_string())
     } else {
         Ok("No local data found".to_string())
@@ -87,8 +293,8 @@ pub async fn load_local_data<R: Runtime>(
 }
 
 #[tauri::command]
-pub fn get_champions(state: State<'_, AppState>) -> Result<Vec<Champion>, String> {
-    let data = state.0.lock().map_err(|_| "Failed to lock state")?;
+pub fn get_champions(state: State<'_, AppState>) -> Result<Vec<Champion>, AppError> {
+    let data = state.data.lock()?;
 
     match &data.champions {
         Some(champ_data) => {
@@ -97,6 +303,359 @@ pub fn get_champions(state: State<'_, AppState>) -> Result<Vec<Champion>, String
 
             Ok(champs)
         }
-        None => Err("Champion data not loaded in memory. Please load data first.".to_string()),
+        None => Err(AppError::not_in_memory("Champion")),
+    }
+}
+
+// Filtered, sorted and paginated champion list, so the UI only receives one page
+#[tauri::command]
+pub fn search_champions(
+    state: State<'_, AppState>,
+    query: Option<ChampionQuery>,
+) -> Result<ChampionPage, AppError> {
+    let data = state.data.lock()?;
+    let champ_data = data
+        .champions
+        .as_ref()
+        .ok_or_else(|| AppError::not_in_memory("Champion"))?;
+
+    search::search(champ_data.data.values(), &query.unwrap_or_default())
+}
+
+// Base stats plus per-level growth for one champion at a level between 1 and 18
+#[tauri::command]
+pub fn get_champion_stats_at_level(
+    state: State<'_, AppState>,
+    id: String,
+    level: u8,
+) -> Result<LevelStats, AppError> {
+    let data = state.data.lock()?;
+    let champion = find_champion(&data, &id)?;
+
+    champion
+        .stats
+        .at_level(level)
+        .ok_or_else(|| invalid_level(level))
+}
+
+// Level by level stat table for a lane matchup, defaults to levels 1-18
+#[tauri::command]
+pub fn compare_champions(
+    state: State<'_, AppState>,
+    ids: Vec<String>,
+    level_range: Option<LevelRange>,
+) -> Result<ChampionComparison, AppError> {
+    let range = level_range.unwrap_or_default();
+    if !range.is_valid() {
+        return Err(AppError::InvalidInput(format!(
+            "Level range must be within {} and {}, got {}-{}",
+            MIN_LEVEL, MAX_LEVEL, range.from, range.to
+        )));
+    }
+
+    let mut unique_ids = ids.clone();
+    unique_ids.sort();
+    unique_ids.dedup();
+    if unique_ids.len() < 2 || unique_ids.len() != ids.len() {
+        return Err(AppError::InvalidInput(
+            "Pick at least two different champions to compare".to_string(),
+        ));
+    }
+
+    let data = state.data.lock()?;
+    let champions = ids
+        .iter()
+        .map(|id| find_champion(&data, id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(compare::compare_champions(&champions, range))
+}
+
+// Effective HP against physical and magic damage at a level, optionally with an item set
+#[tauri::command]
+pub fn get_champion_durability(
+    state: State<'_, AppState>,
+    id: String,
+    level: u8,
+    item_ids: Option<Vec<String>>,
+) -> Result<Durability, AppError> {
+    let data = state.data.lock()?;
+    let champion = find_champion(&data, &id)?;
+    let items = find_items(&data, &item_ids.unwrap_or_default())?;
+
+    durability::durability(&champion.stats, level, &items).ok_or_else(|| invalid_level(level))
+}
+
+// Sustained auto-attack DPS against a target's armor, optionally with an item set
+#[tauri::command]
+pub fn calculate_auto_attack_dps(
+    state: State<'_, AppState>,
+    id: String,
+    level: u8,
+    item_ids: Option<Vec<String>>,
+    target_armor: Option<f64>,
+) -> Result<AutoAttackDps, AppError> {
+    let data = state.data.lock()?;
+    let champion = find_champion(&data, &id)?;
+    let items = find_items(&data, &item_ids.unwrap_or_default())?;
+
+    dps::auto_attack_dps(&champion.stats, level, &items, target_armor.unwrap_or(0.0))
+        .ok_or_else(|| invalid_level(level))
+}
+
+fn invalid_level(level: u8) -> AppError {
+    AppError::InvalidInput(format!(
+        "Level must be between {} and {}, got {}",
+        MIN_LEVEL, MAX_LEVEL, level
+    ))
+}
+
+pub fn find_champion<'a>(data: &'a LeagueDataState, id: &str) -> Result<&'a Champion, AppError> {
+    data.champions
+        .as_ref()
+        .ok_or_else(|| AppError::not_in_memory("Champion"))?
+        .data
+        .get(id)
+        .ok_or_else(|| AppError::NotFound(format!("Unknown champion: {}", id)))
+}
+
+// Items are looked up by their item.json id, e.g. "3075" for Thornmail
+pub fn find_items<'a>(
+    data: &'a LeagueDataState,
+    ids: &[String],
+) -> Result<Vec<&'a Item>, AppError> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let item_data = data
+        .items
+        .as_ref()
+        .ok_or_else(|| AppError::not_in_memory("Item"))?;
+    ids.iter()
+        .map(|id| {
+            item_data
+                .data
+                .get(id)
+                .ok_or_else(|| AppError::NotFound(format!("Unknown item: {}", id)))
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_runes(state: State<'_, AppState>) -> Result<Vec<RunePath>, AppError> {
+    let data = state.data.lock()?;
+
+    match &data.runes {
+        Some(runes) => Ok(runes.clone()),
+        None => Err(AppError::not_in_memory("Rune")),
+    }
+}
+
+// Filters by game mode (CLASSIC, ARAM, URF...) when one is given
+#[tauri::command]
+pub fn get_summoner_spells(
+    state: State<'_, AppState>,
+    mode: Option<String>,
+) -> Result<Vec<SummonerSpell>, AppError> {
+    let data = state.data.lock()?;
+
+    match &data.summoner_spells {
+        Some(summoner_data) => {
+            let mut spells: Vec<SummonerSpell> = summoner_data
+                .data
+                .values()
+                .filter(|spell| mode.as_deref().is_none_or(|m| spell.is_available_in(m)))
+                .cloned()
+                .collect();
+            spells.sort_by(|a, b| a.name.cmp(&b.name));
+
+            Ok(spells)
+        }
+        None => Err(AppError::not_in_memory("Summoner spell")),
+    }
+}
+
+// Shared by get_champion_detail and the AI context builder
+pub async fn champion_detail<R: Runtime>(
+    app: AppHandle<R>,
+    state: &AppState,
+    id: &str,
+) -> Result<ChampionDetail, AppError> {
+    {
+        let data = state.data.lock()?;
+        if let Some(detail) = data.champion_details.get(id) {
+            return Ok(detail.clone());
+        }
+
+        // Only ids from the loaded summary are valid, this also keeps the id out of odd file paths
+        match &data.champions {
+            Some(champ_data) if champ_data.data.contains_key(id) => {}
+            Some(_) => return Err(AppError::NotFound(format!("Unknown champion: {}", id))),
+            None => return Err(AppError::not_in_memory("Champion")),
+        }
     }
+
+    // A cache miss writes champion/{id}.json and the manifest into the live locale dir
+    let guard = state.begin_write()?;
+    let manager = DataManager::new(app).with_cancellation(guard.token());
+    let detail = manager.load_champion_detail(id).await?;
+
+    let mut data = state.data.lock()?;
+    data.champion_details.insert(id.to_string(), detail.clone());
+
+    Ok(detail)
+}
+
+#[tauri::command]
+pub async fn get_champion_detail<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<ChampionDetail, AppError> {
+    champion_detail(app, &state, &id).await
+}
+
+#[tauri::command]
+pub async fn list_locales<R: Runtime>(app: AppHandle<R>) -> Result<Vec<LocaleInfo>, AppError> {
+    let manager = DataManager::new(app);
+    manager.list_locales().await
+}
+
+// Switches the active locale, only downloading when it is not cached for the local version yet
+#[tauri::command]
+pub async fn set_locale<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+    locale: String,
+) -> Result<String, AppError> {
+    let guard = state.begin_write()?;
+    let manager = DataManager::new(app.clone())
+        .with_locale(locale.clone())
+        .with_cancellation(guard.token());
+
+    let locales = manager.fetch_locales().await?;
+    if !locales.contains(&locale) {
+        return Err(AppError::NotFound(format!("Unknown locale: {}", locale)));
+    }
+
+    if let Some(version) = manager.get_local_version() {
+        if !manager.has_locale_data(&version, &locale) {
+            manager.update_data(version).await?;
+        }
+
+        let loaded = manager.load_data()?;
+        let mut data = state.data.lock()?;
+        *data = loaded;
+    }
+
+    // Saved last so a failed download keeps the previous locale active
+    let mut app_settings = settings::load_settings(&app);
+    app_settings.locale = locale.clone();
+    settings::store_settings(&app, &app_settings)?;
+
+    Ok(locale)
+}
+
+// Compares two local versions in the active locale, e.g. to show what a patch changed
+#[tauri::command]
+pub async fn diff_versions<R: Runtime>(
+    app: AppHandle<R>,
+    from: String,
+    to: String,
+) -> Result<VersionDiff, AppError> {
+    let manager = DataManager::new(app);
+
+    // Verifying and parsing two whole versions takes a while, keep it off the main thread
+    tauri::async_runtime::spawn_blocking(move || {
+        let old = manager.read_version(&from)?;
+        let new = manager.read_version(&to)?;
+
+        match (&old.champions, &old.items, &new.champions, &new.items) {
+            (Some(old_champs), Some(old_items), Some(new_champs), Some(new_items)) => Ok(
+                diff::diff_versions(&from, &to, old_champs, old_items, new_champs, new_items),
+            ),
+            _ => Err(AppError::Parse("Version data is incomplete".to_string())),
+        }
+    })
+    .await?
+}
+
+// Offline bootstrap: registers the data from a dragontail tarball or exported zip as local versions
+#[tauri::command]
+pub async fn import_data_archive<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<Vec<ImportedData>, AppError> {
+    let guard = state.begin_write()?;
+    let manager = DataManager::new(app).with_cancellation(guard.token());
+
+    // Dragontail archives are over a gigabyte, keep the extraction off the async runtime
+    let (manager, result) = tauri::async_runtime::spawn_blocking(move || {
+        let result = manager.import_archive(&PathBuf::from(path));
+        (manager, result)
+    })
+    .await?;
+    let imported = result?;
+
+    // Without any active data (e.g. first launch offline) the newest import becomes active
+    if manager.get_local_version().is_none() {
+        let newest = manager
+            .list_local_versions()?
+            .into_iter()
+            .find(|local| local.locales.iter().any(|l| l == manager.locale()));
+        if let Some(newest) = newest {
+            let loaded = manager.load_version(&newest.version)?;
+            manager.set_active_version(&newest.version)?;
+
+            let mut data = state.data.lock()?;
+            *data = loaded;
+        }
+    }
+
+    Ok(imported)
+}
+
+// Snapshot of the active version for sharing, see import_data_archive for the other side
+#[tauri::command]
+pub async fn export_data_archive<R: Runtime>(
+    app: AppHandle<R>,
+    path: String,
+) -> Result<ExportInfo, AppError> {
+    let manager = DataManager::new(app);
+    tauri::async_runtime::spawn_blocking(move || manager.export_archive(&PathBuf::from(path)))
+        .await?
+}
+
+// Downloads icons and sprite sheets of the loaded data so the frontend can use league-asset:// offline
+#[tauri::command]
+pub async fn cache_images<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, AppState>,
+) -> Result<DownloadReport, AppError> {
+    let guard = state.begin_write()?;
+    let manager = DataManager::new(app).with_cancellation(guard.token());
+    let version = manager
+        .get_local_version()
+        .ok_or_else(AppError::no_local_data)?;
+
+    let requests = {
+        let data = state.data.lock()?;
+        if data.champions.is_none() {
+            return Err(AppError::not_in_memory("Champion"));
+        }
+        assets::image_requests(&data, &version)
+    };
+
+    manager.download_images(&version, requests).await
+}
+
+// Shows which fields of the loaded data were unknown or filled with defaults
+#[tauri::command]
+pub fn get_validation_report(state: State<'_, AppState>) -> Result<ValidationReport, AppError> {
+    let data = state.data.lock()?;
+    data.validation
+        .clone()
+        .ok_or_else(|| AppError::not_in_memory("Champion"))
 }
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+// runesReforged.json is a plain array of rune paths (Precision, Domination, ...)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RunePath {
+    pub id: u32,
+    #[serde(default)]
+    pub key: String,
+    #[serde(default)]
+    pub icon: String,
+    pub name: String,
+    #[serde(default)]
+    pub slots: Vec<RuneSlot>,
+}
+
+// First slot of a path holds the keystones, the remaining ones the minor runes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RuneSlot {
+    #[serde(default)]
+    pub runes: Vec<Rune>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Rune {
+    pub id: u32,
+    #[serde(default)]
+    pub key: String,
+    #[serde(default)]
+    pub icon: String,
+    pub name: String,
+    #[serde(rename = "shortDesc", default)]
+    pub short_desc: String,
+    #[serde(rename = "longDesc", default)]
+    pub long_desc: String,
+}
@@ -0,0 +1,222 @@
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::error::AppError;
+
+const IMAGE_FIELDS: &[&str] = &["full", "sprite", "group", "x", "y", "w", "h"];
+
+// Fields the app relies on, listed per object path. Paths use "*" for the entries of a
+// top-level data map and "[]" for array elements. Missing ones are filled with defaults
+// and show up in the report instead of failing the load.
+const CHAMPION_FIELDS: &[(&str, &[&str])] = &[
+    (
+        "data.*",
+        &[
+            "id", "key", "name", "title", "blurb", "info", "image", "tags", "partype", "stats",
+        ],
+    ),
+    ("data.*.info", &["attack", "defense", "magic", "difficulty"]),
+    ("data.*.image", IMAGE_FIELDS),
+    (
+        "data.*.stats",
+        &[
+            "hp",
+            "hpperlevel",
+            "mp",
+            "mpperlevel",
+            "movespeed",
+            "armor",
+            "armorperlevel",
+            "spellblock",
+            "spellblockperlevel",
+            "attackrange",
+            "hpregen",
+            "hpregenperlevel",
+            "mpregen",
+            "mpregenperlevel",
+            "crit",
+            "critperlevel",
+            "attackdamage",
+            "attackdamageperlevel",
+            "attackspeedperlevel",
+            "attackspeed",
+        ],
+    ),
+];
+
+const ITEM_FIELDS: &[(&str, &[&str])] = &[
+    ("data.*", &["name", "description", "image", "gold"]),
+    ("data.*.gold", &["base", "total", "sell", "purchasable"]),
+    ("data.*.image", IMAGE_FIELDS),
+];
+
+const RUNE_FIELDS: &[(&str, &[&str])] = &[
+    ("[]", &["id", "key", "icon", "name", "slots"]),
+    ("[].slots[]", &["runes"]),
+    (
+        "[].slots[].runes[]",
+        &["id", "key", "icon", "name", "shortDesc", "longDesc"],
+    ),
+];
+
+const SUMMONER_FIELDS: &[(&str, &[&str])] = &[
+    (
+        "data.*",
+        &["id", "key", "name", "cooldown", "range", "modes", "image"],
+    ),
+    ("data.*.image", IMAGE_FIELDS),
+];
+
+// Fields Data Dragon always ships that the app deliberately doesn't model. They are left
+// out of unknown_fields, together with anything nested below them, so the report only
+// shows real drift.
+const ITEM_IGNORED: &[&str] = &["basic.group", "data.*.group"];
+
+const SUMMONER_IGNORED: &[&str] = &[
+    "data.*.maxrank",
+    "data.*.cost",
+    "data.*.costBurn",
+    "data.*.costType",
+    "data.*.datavalues",
+    "data.*.effect",
+    "data.*.effectBurn",
+    "data.*.vars",
+    "data.*.maxammo",
+    "data.*.resource",
+];
+
+struct Schema {
+    expected: &'static [(&'static str, &'static [&'static str])],
+    ignored: &'static [&'static str],
+}
+
+impl Schema {
+    fn is_ignored(&self, path: &str) -> bool {
+        self.ignored.iter().any(|ignored| {
+            path.strip_prefix(ignored)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+        })
+    }
+}
+
+// Schema drift found while loading one version, viewable through get_validation_report
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ValidationReport {
+    pub files: Vec<FileReport>,
+}
+
+impl ValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.files
+            .iter()
+            .all(|file| file.unknown_fields.is_empty() && file.missing_fields.is_empty())
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FileReport {
+    pub file: String,
+    // In the data but not modelled by the app, usually new fields from a patch
+    pub unknown_fields: Vec<FieldIssue>,
+    // Expected but absent, the loaded value is a default
+    pub missing_fields: Vec<FieldIssue>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FieldIssue {
+    pub path: String,
+    // How many entries (champions, items, ...) had the issue
+    pub occurrences: usize,
+}
+
+// Parses a Data Dragon file and records how its fields differ from what the app expects
+pub fn parse_checked<T: DeserializeOwned + Serialize>(
+    file: &str,
+    content: &str,
+) -> Result<(T, FileReport), AppError> {
+    let parse_error = |e: serde_json::Error| AppError::Parse(format!("{} Parse: {}", file, e));
+    let raw: Value = serde_json::from_str(content).map_err(parse_error)?;
+    let parsed: T = serde_json::from_value(raw.clone()).map_err(parse_error)?;
+
+    // Round-tripping the parsed value gives every field the structs know about
+    let known = serde_json::to_value(&parsed)?;
+    let mut unknown = BTreeMap::new();
+    let mut missing = BTreeMap::new();
+    compare(&raw, &known, "", &schema(file), &mut unknown, &mut missing);
+
+    let issues = |counts: BTreeMap<String, usize>| {
+        counts
+            .into_iter()
+            .map(|(path, occurrences)| FieldIssue { path, occurrences })
+            .collect()
+    };
+    let report = FileReport {
+        file: file.to_string(),
+        unknown_fields: issues(unknown),
+        missing_fields: issues(missing),
+    };
+    Ok((parsed, report))
+}
+
+fn schema(file: &str) -> Schema {
+    let (expected, ignored): (_, &[&str]) = match file {
+        "champion.json" => (CHAMPION_FIELDS, &[]),
+        "item.json" => (ITEM_FIELDS, ITEM_IGNORED),
+        "runesReforged.json" => (RUNE_FIELDS, &[]),
+        "summoner.json" => (SUMMONER_FIELDS, SUMMONER_IGNORED),
+        _ => (&[], &[]),
+    };
+    Schema { expected, ignored }
+}
+
+fn compare(
+    raw: &Value,
+    known: &Value,
+    path: &str,
+    schema: &Schema,
+    unknown: &mut BTreeMap<String, usize>,
+    missing: &mut BTreeMap<String, usize>,
+) {
+    match (raw, known) {
+        (Value::Object(raw), Value::Object(known)) => {
+            if let Some((_, fields)) = schema.expected.iter().find(|(pattern, _)| *pattern == path)
+            {
+                for field in fields.iter().filter(|field| !raw.contains_key(**field)) {
+                    *missing.entry(child_path(path, field)).or_default() += 1;
+                }
+            }
+
+            for (key, raw_value) in raw {
+                // Map entries are keyed by id, collapse them so each field is reported once
+                let child = if path == "data" {
+                    "data.*".to_string()
+                } else {
+                    child_path(path, key)
+                };
+                match known.get(key) {
+                    Some(known_value) => {
+                        compare(raw_value, known_value, &child, schema, unknown, missing)
+                    }
+                    None if schema.is_ignored(&child) => {}
+                    None => *unknown.entry(child).or_default() += 1,
+                }
+            }
+        }
+        (Value::Array(raw), Value::Array(known)) => {
+            let child = format!("{}[]", path);
+            for (raw_value, known_value) in raw.iter().zip(known) {
+                compare(raw_value, known_value, &child, schema, unknown, missing);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
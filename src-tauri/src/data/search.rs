@@ -0,0 +1,481 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    data::champion::{Champion, ChampionStats},
+    error::AppError,
+};
+
+// Data Dragon has melee champions up to 250 range and ranged ones from 425
+const MELEE_RANGE_MAX: f64 = 350.0;
+
+const DEFAULT_PAGE_SIZE: usize = 24;
+const MAX_PAGE_SIZE: usize = 200;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AttackType {
+    Melee,
+    Ranged,
+}
+
+// Every filter is optional, an empty query returns all champions sorted by name
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ChampionQuery {
+    pub text: String,
+    // A champion matches if it has any of these tags
+    pub tags: Vec<String>,
+    // Resource type such as "Mana" or "Energy", any of these matches
+    pub partypes: Vec<String>,
+    pub min_difficulty: Option<u8>,
+    pub max_difficulty: Option<u8>,
+    pub attack_type: Option<AttackType>,
+    // "name", "relevance", "attack", "defense", "magic", "difficulty" or a
+    // Data Dragon stat key like "attackdamage". Defaults to relevance when
+    // there is search text and name otherwise
+    pub sort_by: Option<String>,
+    pub descending: bool,
+    // 1-based
+    pub page: usize,
+    pub page_size: usize,
+}
+
+impl Default for ChampionQuery {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            tags: Vec::new(),
+            partypes: Vec::new(),
+            min_difficulty: None,
+            max_difficulty: None,
+            attack_type: None,
+            sort_by: None,
+            descending: false,
+            page: 1,
+            page_size: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ChampionPage {
+    pub champions: Vec<Champion>,
+    // Matches across all pages
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+    pub total_pages: usize,
+}
+
+enum SortKey {
+    Name,
+    Relevance,
+    Value(fn(&Champion) -> f64),
+    Stat(&'static str),
+}
+
+impl SortKey {
+    fn parse(key: &str) -> Result<Self, AppError> {
+        let key = key.to_lowercase();
+        let sort = match key.as_str() {
+            "name" => SortKey::Name,
+            "relevance" => SortKey::Relevance,
+            "attack" => SortKey::Value(|c| c.info.attack as f64),
+            "defense" => SortKey::Value(|c| c.info.defense as f64),
+            "magic" => SortKey::Value(|c| c.info.magic as f64),
+            "difficulty" => SortKey::Value(|c| c.info.difficulty as f64),
+            _ => {
+                let stat = ChampionStats::default()
+                    .entries()
+                    .into_iter()
+                    .map(|(stat, _)| stat)
+                    .find(|stat| *stat == key)
+                    .ok_or_else(|| AppError::InvalidInput(format!("Unknown sort key: {}", key)))?;
+                SortKey::Stat(stat)
+            }
+        };
+        Ok(sort)
+    }
+}
+
+pub fn search<'a>(
+    champions: impl Iterator<Item = &'a Champion>,
+    query: &ChampionQuery,
+) -> Result<ChampionPage, AppError> {
+    if query.page == 0 {
+        return Err(AppError::InvalidInput(
+            "Page numbers start at 1".to_string(),
+        ));
+    }
+    if let (Some(min), Some(max)) = (query.min_difficulty, query.max_difficulty) {
+        if min > max {
+            return Err(AppError::InvalidInput(format!(
+                "Invalid difficulty range: {}-{}",
+                min, max
+            )));
+        }
+    }
+
+    let text = normalize(&query.text);
+    let sort = match &query.sort_by {
+        Some(key) => SortKey::parse(key)?,
+        None if text.is_empty() => SortKey::Name,
+        None => SortKey::Relevance,
+    };
+
+    let mut matches: Vec<(u8, &Champion)> = champions
+        .filter(|c| passes_filters(c, query))
+        .filter_map(|c| {
+            if text.is_empty() {
+                Some((0, c))
+            } else {
+                match_score(&text, c).map(|score| (score, c))
+            }
+        })
+        .collect();
+
+    matches.sort_by(|(score_a, a), (score_b, b)| {
+        let ordering = match sort {
+            SortKey::Name => a.name.cmp(&b.name),
+            SortKey::Relevance => score_a.cmp(score_b),
+            SortKey::Value(value) => value(a).total_cmp(&value(b)),
+            SortKey::Stat(key) => stat(a, key).total_cmp(&stat(b, key)),
+        };
+        let ordering = if query.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+        // Name breaks ties so pages stay stable between requests
+        ordering.then_with(|| a.name.cmp(&b.name))
+    });
+
+    let page_size = query.page_size.clamp(1, MAX_PAGE_SIZE);
+    let total = matches.len();
+    let total_pages = total.div_ceil(page_size);
+    let champions = matches
+        .into_iter()
+        .skip((query.page - 1).saturating_mul(page_size))
+        .take(page_size)
+        .map(|(_, c)| c.clone())
+        .collect();
+
+    Ok(ChampionPage {
+        champions,
+        total,
+        page: query.page,
+        page_size,
+        total_pages,
+    })
+}
+
+fn stat(champion: &Champion, key: &str) -> f64 {
+    champion
+        .stats
+        .entries()
+        .into_iter()
+        .find(|(k, _)| *k == key)
+        .map_or(0.0, |(_, v)| v)
+}
+
+fn passes_filters(champion: &Champion, query: &ChampionQuery) -> bool {
+    let has_any = |wanted: &[String], values: &[&str]| {
+        wanted.is_empty()
+            || wanted
+                .iter()
+                .any(|w| values.iter().any(|v| v.eq_ignore_ascii_case(w)))
+    };
+    let tags: Vec<&str> = champion.tags.iter().map(String::as_str).collect();
+
+    let difficulty = champion.info.difficulty;
+    let is_melee = champion.stats.attack_range <= MELEE_RANGE_MAX;
+
+    has_any(&query.tags, &tags)
+        && has_any(&query.partypes, &[champion.partype.as_str()])
+        && query.min_difficulty.is_none_or(|min| difficulty >= min)
+        && query.max_difficulty.is_none_or(|max| difficulty <= max)
+        && match query.attack_type {
+            Some(AttackType::Melee) => is_melee,
+            Some(AttackType::Ranged) => !is_melee,
+            None => true,
+        }
+}
+
+// Lower is better, None when the champion does not match at all
+fn match_score(text: &str, champion: &Champion) -> Option<u8> {
+    let name = normalize(&champion.name);
+    let id = normalize(&champion.id);
+    let title = normalize(&champion.title);
+    // "Dr. Mundo" -> ["dr", "mundo"], so "mundo" scores as a word prefix
+    let words: Vec<String> = champion
+        .name
+        .split(|ch: char| ch.is_whitespace() || ch == '.' || ch == '&')
+        .map(normalize)
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    if name == text || id == text {
+        Some(0)
+    } else if name.starts_with(text) || words.iter().any(|w| w.starts_with(text)) {
+        Some(1)
+    } else if name.contains(text) || id.contains(text) {
+        Some(2)
+    } else if title.contains(text) {
+        Some(3)
+    } else if is_subsequence(text, &name) {
+        Some(4)
+    } else if is_typo(text, &name) || words.iter().any(|w| is_typo(text, w)) {
+        Some(5)
+    } else {
+        None
+    }
+}
+
+// "mf" style abbreviations, every query character appears in order
+fn is_subsequence(text: &str, target: &str) -> bool {
+    let mut chars = target.chars();
+    text.len() > 1 && text.chars().all(|c| chars.any(|t| t == c))
+}
+
+// Allow one typo per four characters so short queries don't match everything
+fn is_typo(text: &str, target: &str) -> bool {
+    let allowed = text.chars().count() / 4;
+    allowed > 0 && edit_distance(text, target) <= allowed
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+// Lowercase, fold accents and drop punctuation so "Kai'Sa" and "kaisa" compare equal
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(fold_accent)
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+fn fold_accent(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'ď' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => 'i',
+        'ł' => 'l',
+        'ñ' | 'ń' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => 'o',
+        'ř' => 'r',
+        'ś' | 'š' | 'ş' => 's',
+        'ť' | 'ţ' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::champion::ChampionInfo;
+
+    fn champion(name: &str, title: &str, tags: &[&str], difficulty: u8, range: f64) -> Champion {
+        Champion {
+            id: name.replace([' ', '.', '\''], ""),
+            key: String::new(),
+            name: name.to_string(),
+            title: title.to_string(),
+            version: String::new(),
+            blurb: String::new(),
+            info: ChampionInfo {
+                difficulty,
+                ..Default::default()
+            },
+            image: Default::default(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            partype: "Mana".to_string(),
+            stats: ChampionStats {
+                attack_range: range,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn roster() -> Vec<Champion> {
+        vec![
+            champion(
+                "Dr. Mundo",
+                "the Madman of Zaun",
+                &["Tank", "Fighter"],
+                5,
+                125.0,
+            ),
+            champion("Kai'Sa", "Daughter of the Void", &["Marksman"], 6, 525.0),
+            champion(
+                "Ahri",
+                "the Nine-Tailed Fox",
+                &["Mage", "Assassin"],
+                5,
+                550.0,
+            ),
+            champion(
+                "Garen",
+                "The Might of Demacia",
+                &["Fighter", "Tank"],
+                2,
+                175.0,
+            ),
+        ]
+    }
+
+    fn names(page: &ChampionPage) -> Vec<&str> {
+        page.champions.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    fn query(text: &str) -> ChampionQuery {
+        ChampionQuery {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn normalize_folds_case_accents_and_punctuation() {
+        assert_eq!(normalize("Kai'Sa"), "kaisa");
+        assert_eq!(normalize("Dr. Mundo"), "drmundo");
+        assert_eq!(normalize("Nunu & Willump"), "nunuwillump");
+        assert_eq!(normalize("Maître Yi"), "maitreyi");
+        assert_eq!(normalize("ÉLISE"), "elise");
+    }
+
+    #[test]
+    fn fold_accent_leaves_plain_characters_alone() {
+        assert_eq!(fold_accent('é'), 'e');
+        assert_eq!(fold_accent('ñ'), 'n');
+        assert_eq!(fold_accent('z'), 'z');
+        assert_eq!(fold_accent('7'), '7');
+    }
+
+    #[test]
+    fn match_score_ranks_exact_prefix_substring_title_abbreviation_typo() {
+        let mundo = &roster()[0];
+        assert_eq!(match_score(&normalize("Dr. Mundo"), mundo), Some(0));
+        assert_eq!(match_score(&normalize("DrMundo"), mundo), Some(0));
+        assert_eq!(match_score(&normalize("mundo"), mundo), Some(1));
+        assert_eq!(match_score(&normalize("MUNDO"), mundo), Some(1));
+        assert_eq!(match_score(&normalize("rmun"), mundo), Some(2));
+        assert_eq!(match_score(&normalize("madman"), mundo), Some(3));
+        assert_eq!(match_score(&normalize("dmd"), mundo), Some(4));
+        assert_eq!(match_score(&normalize("mubdo"), mundo), Some(5));
+        assert_eq!(match_score(&normalize("ahri"), mundo), None);
+    }
+
+    #[test]
+    fn match_score_ignores_accents_and_apostrophes() {
+        let kaisa = &roster()[1];
+        assert_eq!(match_score(&normalize("kaisa"), kaisa), Some(0));
+        assert_eq!(match_score(&normalize("KAÏSA"), kaisa), Some(0));
+    }
+
+    #[test]
+    fn subsequence_needs_order_and_two_characters() {
+        assert!(is_subsequence("dmd", "drmundo"));
+        assert!(!is_subsequence("dmr", "drmundo"));
+        assert!(!is_subsequence("d", "drmundo"));
+    }
+
+    #[test]
+    fn edit_distance_counts_insertions_deletions_and_substitutions() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("garen", "garen"), 0);
+    }
+
+    #[test]
+    fn typos_are_allowed_one_per_four_characters() {
+        assert!(is_typo("garem", "garen"));
+        assert!(!is_typo("gxrem", "garen"));
+        assert!(!is_typo("ahr", "ahri"));
+    }
+
+    #[test]
+    fn search_finds_mundo_by_last_name() {
+        let roster = roster();
+        let page = search(roster.iter(), &query("mundo")).unwrap();
+        assert_eq!(names(&page), ["Dr. Mundo"]);
+    }
+
+    #[test]
+    fn search_filters_by_tag_difficulty_and_range() {
+        let roster = roster();
+        let melee_tanks = ChampionQuery {
+            tags: vec!["tank".to_string()],
+            attack_type: Some(AttackType::Melee),
+            max_difficulty: Some(3),
+            ..Default::default()
+        };
+        let page = search(roster.iter(), &melee_tanks).unwrap();
+        assert_eq!(names(&page), ["Garen"]);
+
+        let ranged = ChampionQuery {
+            attack_type: Some(AttackType::Ranged),
+            ..Default::default()
+        };
+        let page = search(roster.iter(), &ranged).unwrap();
+        assert_eq!(names(&page), ["Ahri", "Kai'Sa"]);
+    }
+
+    #[test]
+    fn search_sorts_by_stat_and_rejects_unknown_keys() {
+        let roster = roster();
+        let by_range = ChampionQuery {
+            sort_by: Some("attackrange".to_string()),
+            descending: true,
+            ..Default::default()
+        };
+        let page = search(roster.iter(), &by_range).unwrap();
+        assert_eq!(names(&page), ["Ahri", "Kai'Sa", "Garen", "Dr. Mundo"]);
+
+        let unknown = ChampionQuery {
+            sort_by: Some("lethality".to_string()),
+            ..Default::default()
+        };
+        assert!(search(roster.iter(), &unknown).is_err());
+    }
+
+    #[test]
+    fn search_paginates_and_survives_huge_pages() {
+        let roster = roster();
+        let second = ChampionQuery {
+            page: 2,
+            page_size: 3,
+            ..Default::default()
+        };
+        let page = search(roster.iter(), &second).unwrap();
+        assert_eq!(names(&page), ["Kai'Sa"]);
+        assert_eq!((page.total, page.total_pages), (4, 2));
+
+        let huge = ChampionQuery {
+            page: usize::MAX,
+            ..Default::default()
+        };
+        let page = search(roster.iter(), &huge).unwrap();
+        assert!(page.champions.is_empty());
+        assert_eq!(page.total, 4);
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::data::Image;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SummonerData {
+    #[serde(rename = "type", default)]
+    pub data_type: String,
+    #[serde(default)]
+    pub version: String,
+    pub data: HashMap<String, SummonerSpell>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SummonerSpell {
+    pub id: String,
+    #[serde(default)]
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tooltip: String,
+
+    // Per rank values, summoner spells only have a single rank
+    #[serde(default)]
+    pub cooldown: Vec<f64>,
+    #[serde(rename = "cooldownBurn", default)]
+    pub cooldown_burn: String,
+    #[serde(default)]
+    pub range: Vec<f64>,
+    #[serde(rename = "rangeBurn", default)]
+    pub range_burn: String,
+
+    #[serde(rename = "summonerLevel", default)]
+    pub summoner_level: u32,
+    // Game modes the spell is available in, e.g. CLASSIC, ARAM, URF
+    #[serde(default)]
+    pub modes: Vec<String>,
+    #[serde(default)]
+    pub image: Image,
+}
+
+impl SummonerSpell {
+    pub fn is_available_in(&self, mode: &str) -> bool {
+        self.modes.iter().any(|m| m.eq_ignore_ascii_case(mode))
+    }
+}
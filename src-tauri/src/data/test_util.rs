@@ -0,0 +1,12 @@
+// Shared by the unit tests of the stat calculations
+
+const TOLERANCE: f64 = 1e-9;
+
+pub fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < TOLERANCE,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
@@ -0,0 +1,75 @@
+use serde::Serialize;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime, State};
+
+use crate::data::{manager::DataManager, run_update, AppState};
+use crate::error::AppError;
+use crate::settings;
+
+pub const UPDATE_AVAILABLE_EVENT: &str = "data-update-available";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateAvailable {
+    pub current_version: Option<String>,
+    pub latest_version: String,
+    // True when auto_download_updates is on and the download has been started
+    pub downloading: bool,
+}
+
+// Background task spawned from run(): checks on launch and then on the configured interval.
+// Settings are re-read on every tick so changes apply without restarting the app.
+pub async fn run<R: Runtime>(app: AppHandle<R>) {
+    // Only announce each patch once per session
+    let mut notified: Option<String> = None;
+
+    loop {
+        let settings = settings::load_settings(&app);
+        if settings.auto_check_updates {
+            if let Err(e) = check_once(&app, settings.auto_download_updates, &mut notified).await {
+                println!("Background update check failed: {}", e);
+            }
+        }
+
+        let hours = settings.update_check_interval_hours.max(1);
+        tokio::time::sleep(Duration::from_secs(hours * 60 * 60)).await;
+    }
+}
+
+async fn check_once<R: Runtime>(
+    app: &AppHandle<R>,
+    auto_download: bool,
+    notified: &mut Option<String>,
+) -> Result<(), AppError> {
+    let status = DataManager::new(app.clone()).check_status().await?;
+    // A stale status is just the cached list from when we were last online
+    if status.is_stale
+        || status.is_up_to_date
+        || notified.as_deref() == Some(&status.latest_version)
+    {
+        return Ok(());
+    }
+
+    let available = UpdateAvailable {
+        current_version: status.current_version,
+        latest_version: status.latest_version.clone(),
+        downloading: auto_download,
+    };
+    let state = app.state::<AppState>();
+    // The first check can run before the webview listens, so the UI can also ask for it later
+    *state.pending_update.lock()? = Some(available.clone());
+    let _ = app.emit(UPDATE_AVAILABLE_EVENT, available);
+
+    if auto_download {
+        // Progress and completion go out through the same events as a manual update_data
+        run_update(app.clone(), &state).await?;
+    }
+    // Only mark the patch once it is handled, a failed download is retried on the next tick
+    *notified = Some(status.latest_version);
+    Ok(())
+}
+
+// Update found by the background check that hasn't been installed yet
+#[tauri::command]
+pub fn get_pending_update(state: State<'_, AppState>) -> Result<Option<UpdateAvailable>, AppError> {
+    Ok(state.pending_update.lock()?.clone())
+}
@@ -0,0 +1,165 @@
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::{fmt, io, string::FromUtf8Error, sync::PoisonError};
+
+// Every command error, sent to the frontend as { kind, code, message, status }.
+// kind groups errors for display, code is stable and safe to match on.
+#[derive(Debug, Clone)]
+pub enum AppError {
+    // Data Dragon or another remote could not be reached or kept failing
+    Network(String),
+    Io(String),
+    // A file or response did not have the expected shape
+    Parse(String),
+    // Nothing downloaded or loaded into memory yet
+    NotLoaded(String),
+    NotFound(String),
+    InvalidInput(String),
+    // Local files that fail their manifest check
+    Corrupt(Vec<String>),
+    // Another data update is already running
+    Busy,
+    Keyring(String),
+    // The AI provider answered with a non-success status
+    Provider { status: u16, message: String },
+    Cancelled,
+    Internal(String),
+}
+
+impl AppError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Network(_) => "network",
+            AppError::Io(_) => "io",
+            AppError::Parse(_) => "parse",
+            AppError::NotLoaded(_) => "notLoaded",
+            AppError::NotFound(_) => "notFound",
+            AppError::InvalidInput(_) => "invalidInput",
+            AppError::Corrupt(_) => "corrupt",
+            AppError::Busy => "busy",
+            AppError::Keyring(_) => "keyring",
+            AppError::Provider { .. } => "provider",
+            AppError::Cancelled => "cancelled",
+            AppError::Internal(_) => "internal",
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Network(_) => "E_NETWORK",
+            AppError::Io(_) => "E_IO",
+            AppError::Parse(_) => "E_PARSE",
+            AppError::NotLoaded(_) => "E_NOT_LOADED",
+            AppError::NotFound(_) => "E_NOT_FOUND",
+            AppError::InvalidInput(_) => "E_INVALID_INPUT",
+            AppError::Corrupt(_) => "E_CORRUPT",
+            AppError::Busy => "E_BUSY",
+            AppError::Keyring(_) => "E_KEYRING",
+            AppError::Provider { .. } => "E_PROVIDER",
+            AppError::Cancelled => "E_CANCELLED",
+            AppError::Internal(_) => "E_INTERNAL",
+        }
+    }
+
+    // Shorthand for the most common NotLoaded cases
+    pub fn no_local_data() -> Self {
+        AppError::NotLoaded("No local data found. Please update.".to_string())
+    }
+
+    pub fn not_in_memory(what: &str) -> Self {
+        AppError::NotLoaded(format!(
+            "{} data not loaded in memory. Please load data first.",
+            what
+        ))
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Corrupt(files) => write!(f, "Corrupt data files: {}", files.join(", ")),
+            AppError::Busy => write!(f, "A data update is already running"),
+            AppError::Provider { status, message } => {
+                write!(f, "Provider returned HTTP {}: {}", status, message)
+            }
+            AppError::Cancelled => write!(f, "Data update cancelled"),
+            AppError::Network(message)
+            | AppError::Io(message)
+            | AppError::Parse(message)
+            | AppError::NotLoaded(message)
+            | AppError::NotFound(message)
+            | AppError::InvalidInput(message)
+            | AppError::Keyring(message)
+            | AppError::Internal(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let status = match self {
+            AppError::Provider { status, .. } => Some(*status),
+            _ => None,
+        };
+
+        let mut error = serializer.serialize_struct("AppError", 4)?;
+        error.serialize_field("kind", self.kind())?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.serialize_field("status", &status)?;
+        error.end()
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(e: io::Error) -> Self {
+        AppError::Io(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        AppError::Parse(e.to_string())
+    }
+}
+
+impl From<FromUtf8Error> for AppError {
+    fn from(e: FromUtf8Error) -> Self {
+        AppError::Parse(e.to_string())
+    }
+}
+
+impl From<zip::result::ZipError> for AppError {
+    fn from(e: zip::result::ZipError) -> Self {
+        match e {
+            zip::result::ZipError::Io(e) => AppError::Io(e.to_string()),
+            e => AppError::Parse(e.to_string()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        AppError::Network(e.to_string())
+    }
+}
+
+impl From<keyring::Error> for AppError {
+    fn from(e: keyring::Error) -> Self {
+        AppError::Keyring(e.to_string())
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(e: tauri::Error) -> Self {
+        AppError::Io(e.to_string())
+    }
+}
+
+// A poisoned lock only happens after a panic elsewhere, nothing the user can fix
+impl<T> From<PoisonError<T>> for AppError {
+    fn from(_: PoisonError<T>) -> Self {
+        AppError::Internal("Failed to lock state".to_string())
+    }
+}
@@ -1,5 +1,7 @@
 mod ai;
 mod data;
+mod error;
+mod settings;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -12,16 +14,61 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .manage(data::AppState::default())
+        .register_asynchronous_uri_scheme_protocol(
+            data::assets::ASSET_SCHEME,
+            |ctx, request, responder| {
+                // Sprite cropping decodes PNGs, keep it off the webview thread
+                let app = ctx.app_handle().clone();
+                tauri::async_runtime::spawn_blocking(move || {
+                    responder.respond(data::assets::handle_asset_request(&app, &request));
+                });
+            },
+        )
+        .setup(|app| {
+            // Clean up whatever an interrupted data update left behind
+            let manager = data::manager::DataManager::new(app.handle().clone());
+            if let Err(e) = manager.recover() {
+                println!("Failed to recover league data: {}", e);
+            }
+            tauri::async_runtime::spawn(data::updater::run(app.handle().clone()));
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             ai::send_chat_request,
             ai::save_ai_profile,
             ai::get_ai_profiles,
             ai::delete_ai_profile,
+            ai::get_champion_context,
+            ai::get_patch_context,
             data::get_data_status,
             data::update_data,
+            data::cancel_data_update,
+            data::repair_data,
             data::load_local_data,
-            data::get_champions
+            data::get_champions,
+            data::updater::get_pending_update,
+            data::search_champions,
+            data::get_champion_stats_at_level,
+            data::compare_champions,
+            data::get_champion_durability,
+            data::calculate_auto_attack_dps,
+            data::get_runes,
+            data::get_summoner_spells,
+            data::get_champion_detail,
+            data::list_locales,
+            data::set_locale,
+            data::list_local_versions,
+            data::get_available_versions,
+            data::download_version,
+            data::set_active_version,
+            data::diff_versions,
+            data::import_data_archive,
+            data::export_data_archive,
+            data::cache_images,
+            data::get_validation_report,
+            settings::get_settings,
+            settings::save_settings
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::error::AppError;
+
+const SETTINGS_FILENAME: &str = "settings.json";
+
+pub const DEFAULT_DATA_MIRROR: &str = "https://ddragon.leagueoflegends.com";
+
+// Missing fields fall back to their defaults so older settings files keep loading
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AppSettings {
+    // Data Dragon locale, e.g. en_US, ko_KR, zh_CN
+    pub locale: String,
+    // How many patch versions to keep on disk, the active one is never pruned
+    pub keep_versions: usize,
+
+    // Data Dragon base URLs (serving /api and /cdn), tried in order when one fails
+    pub data_mirrors: Vec<String>,
+
+    // Data Dragon request behaviour
+    pub connect_timeout_secs: u64,
+    pub read_timeout_secs: u64,
+    pub max_retries: u32,
+    // How many files are downloaded at the same time
+    pub download_concurrency: usize,
+
+    // Background check for new patches, on launch and then every update_check_interval_hours
+    pub auto_check_updates: bool,
+    pub update_check_interval_hours: u64,
+    // Download a new patch as soon as the background check finds it
+    pub auto_download_updates: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            locale: "en_US".to_string(),
+            keep_versions: 3,
+            data_mirrors: vec![DEFAULT_DATA_MIRROR.to_string()],
+            connect_timeout_secs: 10,
+            read_timeout_secs: 30,
+            max_retries: 3,
+            download_concurrency: 6,
+            auto_check_updates: true,
+            update_check_interval_hours: 6,
+            auto_download_updates: false,
+        }
+    }
+}
+
+fn get_settings_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, AppError> {
+    let path = app.path().app_config_dir()?;
+    if !path.exists() {
+        fs::create_dir_all(&path)?;
+    }
+    Ok(path.join(SETTINGS_FILENAME))
+}
+
+// Never fails, a missing or unreadable file just means default settings
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> AppSettings {
+    get_settings_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn store_settings<R: Runtime>(
+    app: &AppHandle<R>,
+    settings: &AppSettings,
+) -> Result<(), AppError> {
+    let path = get_settings_path(app)?;
+    let json = serde_json::to_string_pretty(settings)?;
+    fs::write(path, json).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn get_settings<R: Runtime>(app: AppHandle<R>) -> AppSettings {
+    load_settings(&app)
+}
+
+#[tauri::command]
+pub fn save_settings<R: Runtime>(app: AppHandle<R>, settings: AppSettings) -> Result<(), AppError> {
+    store_settings(&app, &settings)
+}
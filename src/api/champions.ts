@@ -1,5 +1,13 @@
 import { invoke } from "@tauri-apps/api/core";
-import { Champion, DataStatus } from "@/types";
+import { listen } from "@tauri-apps/api/event";
+import {
+  Champion,
+  ChampionPage,
+  ChampionQuery,
+  DataStatus,
+  UpdateAvailable,
+  UpdateReport,
+} from "@/types";
 
 export const championApi = {
   getStatus: async () => {
@@ -7,7 +15,18 @@ export const championApi = {
   },
 
   updateData: async () => {
-    return await invoke<string>("update_data");
+    return await invoke<UpdateReport>("update_data");
+  },
+
+  getPendingUpdate: async () => {
+    return await invoke<UpdateAvailable | null>("get_pending_update");
+  },
+
+  // Fired by the background updater, returns the unlisten function
+  onUpdateAvailable: (handler: (update: UpdateAvailable) => void) => {
+    return listen<UpdateAvailable>("data-update-available", (event) =>
+      handler(event.payload),
+    );
   },
 
   loadLocalData: async () => {
@@ -17,4 +36,8 @@ export const championApi = {
   getAll: async () => {
     return await invoke<Champion[]>("get_champions");
   },
+
+  search: async (query: ChampionQuery) => {
+    return await invoke<ChampionPage>("search_champions", { query });
+  },
 };
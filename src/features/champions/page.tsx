@@ -19,14 +19,16 @@ import {
   RiRefreshLine,
   RiSearchLine,
 } from "@remixicon/react";
-import { useEffect, useState } from "react";
+import { useEffect, useRef, useState } from "react";
 import { ChampionCard } from "./components/ChampionCard";
+import { errorMessage } from "@/lib/utils";
 
 const ITEMS_PER_PAGE = 24;
 
 export default function ChampionPage() {
   const [status, setStatus] = useState<DataStatus | null>(null);
   const [champions, setChampions] = useState<Champion[]>([]);
+  const [total, setTotal] = useState(0);
   const [loading, setLoading] = useState(false);
   const [search, setSearch] = useState("");
   const [loadedFromMemory, setLoadedFromMemory] = useState(false);
@@ -34,16 +36,29 @@ export default function ChampionPage() {
   // Pagination State
   const [currentPage, setCurrentPage] = useState(1);
 
+  // Only the latest search request may update the list
+  const requestId = useRef(0);
+
   useEffect(() => {
     checkStatus();
-    // Try to fetch champions immediately in case they are already in memory
-    fetchChampions(true);
   }, []);
 
-  // Reset to page 1 when search changes
+  // Background update checks, including one that finished before we started listening
   useEffect(() => {
-    setCurrentPage(1);
-  }, [search]);
+    championApi
+      .getPendingUpdate()
+      .then((update) => update && checkStatus())
+      .catch(console.error);
+    const unlisten = championApi.onUpdateAvailable(() => checkStatus());
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  // Also runs on mount, in case champions are already in memory
+  useEffect(() => {
+    fetchChampions(true);
+  }, [search, currentPage]);
 
   async function checkStatus() {
     try {
@@ -61,7 +76,7 @@ export default function ChampionPage() {
       await checkStatus();
       await fetchChampions();
     } catch (e) {
-      alert("Update failed: " + e);
+      alert("Update failed: " + errorMessage(e));
     } finally {
       setLoading(false);
     }
@@ -73,7 +88,7 @@ export default function ChampionPage() {
       await championApi.loadLocalData();
       await fetchChampions();
     } catch (e) {
-      alert("Load failed: " + e);
+      alert("Load failed: " + errorMessage(e));
     } finally {
       setLoading(false);
     }
@@ -81,8 +96,15 @@ export default function ChampionPage() {
 
   async function fetchChampions(silent = false) {
     try {
-      const data = await championApi.getAll();
-      setChampions(data);
+      const id = ++requestId.current;
+      const result = await championApi.search({
+        text: search,
+        page: currentPage,
+        page_size: ITEMS_PER_PAGE,
+      });
+      if (id !== requestId.current) return;
+      setChampions(result.champions);
+      setTotal(result.total);
       setLoadedFromMemory(true);
     } catch (e) {
       setLoadedFromMemory(false);
@@ -90,18 +112,9 @@ export default function ChampionPage() {
     }
   }
 
-  // Filter
-  const filtered = champions.filter((c) =>
-    c.name.toLowerCase().includes(search.toLowerCase()),
-  );
-
-  // Paginate
-  const totalPages = Math.ceil(filtered.length / ITEMS_PER_PAGE);
+  // Filtering and paging happen in the backend, champions holds the current page
+  const totalPages = Math.ceil(total / ITEMS_PER_PAGE);
   const startIndex = (currentPage - 1) * ITEMS_PER_PAGE;
-  const paginatedChampions = filtered.slice(
-    startIndex,
-    startIndex + ITEMS_PER_PAGE,
-  );
 
   const handlePageChange = (page: number) => {
     setCurrentPage(page);
@@ -189,13 +202,15 @@ export default function ChampionPage() {
                   placeholder="Search champions..."
                   className="bg-background/50 pl-9"
                   value={search}
-                  onChange={(e) => setSearch(e.target.value)}
+                  onChange={(e) => {
+                    setSearch(e.target.value);
+                    setCurrentPage(1);
+                  }}
                 />
               </div>
               <div className="text-muted-foreground hidden text-xs sm:block">
-                {startIndex + 1}-
-                {Math.min(startIndex + ITEMS_PER_PAGE, filtered.length)} of{" "}
-                {filtered.length}
+                {total === 0 ? 0 : startIndex + 1}-
+                {Math.min(startIndex + ITEMS_PER_PAGE, total)} of {total}
               </div>
             </div>
           )}
@@ -230,12 +245,12 @@ export default function ChampionPage() {
         ) : (
           <div className="flex flex-col gap-8 pb-8">
             <div className="grid grid-cols-1 gap-4 sm:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 2xl:grid-cols-5">
-              {paginatedChampions.map((champ) => (
+              {champions.map((champ) => (
                 <ChampionCard key={champ.key} champion={champ} />
               ))}
             </div>
 
-            {filtered.length === 0 && (
+            {total === 0 && (
               <div className="text-muted-foreground py-12 text-center text-sm">
                 No champions found matching "{search}"
               </div>
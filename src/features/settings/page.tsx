@@ -18,6 +18,7 @@ import {
   RiCloseLine,
 } from "@remixicon/react";
 import { AiProfile } from "@/types";
+import { errorMessage } from "@/lib/utils";
 
 const DEFAULT_FORM_DATA = {
   id: "",
@@ -43,7 +44,7 @@ export default function SettingsPage() {
       setProfiles(data);
     } catch (error) {
       console.error("Failed to load profiles:", error);
-      alert("Failed to load profiles: " + error);
+      alert("Failed to load profiles: " + errorMessage(error));
     }
   }
 
@@ -97,7 +98,7 @@ export default function SettingsPage() {
       resetForm();
     } catch (error) {
       console.error("Failed to save profile:", error);
-      alert("Failed to save profile: " + error);
+      alert("Failed to save profile: " + errorMessage(error));
     } finally {
       setLoading(false);
     }
@@ -112,7 +113,7 @@ export default function SettingsPage() {
       if (formData.id === id) resetForm();
     } catch (error) {
       console.error("Failed to delete profile:", error);
-      alert("Failed to delete profile: " + error);
+      alert("Failed to delete profile: " + errorMessage(error));
     }
   }
 
@@ -10,10 +10,43 @@ export interface ChatMessage {
   content: string;
 }
 
+// Error returned by every backend command, match on code rather than message
+export interface AppError {
+  kind: string;
+  code: string;
+  message: string;
+  status: number | null;
+}
+
 export interface DataStatus {
   current_version: string | null;
   latest_version: string;
   is_up_to_date: boolean;
+  corrupt_files: string[];
+  is_stale: boolean;
+  checked_at: number | null;
+}
+
+export interface FailedDownload {
+  file: string;
+  error: string;
+}
+
+export interface DownloadReport {
+  succeeded: string[];
+  skipped: string[];
+  failed: FailedDownload[];
+}
+
+export interface UpdateReport {
+  version: string;
+  files: DownloadReport;
+}
+
+export interface UpdateAvailable {
+  current_version: string | null;
+  latest_version: string;
+  downloading: boolean;
 }
 
 export interface Image {
@@ -69,3 +102,25 @@ export interface Champion {
   partype: string;
   stats: ChampionStats;
 }
+
+export interface ChampionQuery {
+  text?: string;
+  tags?: string[];
+  partypes?: string[];
+  min_difficulty?: number;
+  max_difficulty?: number;
+  attack_type?: "melee" | "ranged";
+  // "name", "relevance", "attack", "defense", "magic", "difficulty" or a stat key
+  sort_by?: string;
+  descending?: boolean;
+  page?: number;
+  page_size?: number;
+}
+
+export interface ChampionPage {
+  champions: Champion[];
+  total: number;
+  page: number;
+  page_size: number;
+  total_pages: number;
+}